@@ -6,6 +6,7 @@ pub fn landing_page() -> Html {
     let selected_fruit = use_state(|| vec!["apple".to_string()]);
     let selected_veg = use_state(|| vec!["carrot".to_string()]);
     let selected_emojis = use_state(|| vec!["🎉".to_string()]);
+    let selected_food = use_state(Vec::<String>::new);
     let onchange = |state: UseStateHandle<Vec<String>>| {
         Callback::from(move |value: Vec<String>| state.set(value))
     };
@@ -230,6 +231,77 @@ pub fn landing_page() -> Html {
                         </Group>
                     </Select>
                 </div>
+                // Custom Popup with Collapsible Groups
+                <div class="flex flex-col items-center bg-gray-50 p-6 rounded-lg shadow-lg">
+                    <h2 class="text-xl font-semibold mb-4 text-gray-800">
+                        { "Custom Popup with Collapsible Groups" }
+                    </h2>
+                    <pre
+                        class="font-mono text-xs text-gray-200 bg-gray-800 p-4 rounded-md w-full mb-4 overflow-x-auto"
+                    >
+                        { r#"<Select
+    name="custom-select"
+    placeholder="Pick some food"
+    custom=true
+    multiple=true
+    onchange={Callback::from(move |value: Vec<String>| state.set(value))}
+    class="relative w-full"
+    labels_class="flex flex-wrap gap-2 mb-2"
+    label_class="bg-purple-200 text-purple-800 px-3 py-1 rounded-md"
+    close_class="ml-2 text-red-600 hover:text-red-800"
+    select_class="w-full border border-purple-300 rounded-md p-2 cursor-pointer"
+    popup_class="absolute z-10 w-full mt-1 bg-white border rounded-md shadow-lg max-h-60 overflow-auto"
+>
+    <Group label="Fruits" group=true collapsible=true
+        header_class="flex justify-between px-2 py-1 font-semibold cursor-pointer"
+        count_class="bg-purple-600 text-white rounded-full px-2 text-xs">
+        <Option value="apple" label="🍎 Apple" highlighted_class="bg-purple-100" />
+        <Option value="grapes" label="🍇 Grapes" highlighted_class="bg-purple-100" />
+    </Group>
+    <Group label="Vegetables" group=true collapsible=true default_collapsed=true
+        header_class="flex justify-between px-2 py-1 font-semibold cursor-pointer"
+        count_class="bg-purple-600 text-white rounded-full px-2 text-xs">
+        <Option value="carrot" label="🥕 Carrot" highlighted_class="bg-purple-100" />
+        <Option value="broccoli" label="🥦 Broccoli" highlighted_class="bg-purple-100" />
+    </Group>
+</Select>"# }
+                    </pre>
+                    <Select
+                        name="custom-select"
+                        placeholder="Pick some food"
+                        custom=true
+                        multiple=true
+                        onchange={onchange(selected_food.clone())}
+                        class="relative w-full"
+                        labels_class="flex flex-wrap gap-2 mb-2"
+                        label_class="bg-purple-200 text-purple-800 px-3 py-1 rounded-md"
+                        close_class="ml-2 text-red-600 hover:text-red-800"
+                        select_class="w-full border border-purple-300 rounded-md p-2 cursor-pointer"
+                        popup_class="absolute z-10 w-full mt-1 bg-white border rounded-md shadow-lg max-h-60 overflow-auto"
+                    >
+                        <Group
+                            label="Fruits"
+                            group=true
+                            collapsible=true
+                            header_class="flex justify-between px-2 py-1 font-semibold cursor-pointer"
+                            count_class="bg-purple-600 text-white rounded-full px-2 text-xs"
+                        >
+                            <Option value="apple" label="🍎 Apple" highlighted_class="bg-purple-100" />
+                            <Option value="grapes" label="🍇 Grapes" highlighted_class="bg-purple-100" />
+                        </Group>
+                        <Group
+                            label="Vegetables"
+                            group=true
+                            collapsible=true
+                            default_collapsed=true
+                            header_class="flex justify-between px-2 py-1 font-semibold cursor-pointer"
+                            count_class="bg-purple-600 text-white rounded-full px-2 text-xs"
+                        >
+                            <Option value="carrot" label="🥕 Carrot" highlighted_class="bg-purple-100" />
+                            <Option value="broccoli" label="🥦 Broccoli" highlighted_class="bg-purple-100" />
+                        </Group>
+                    </Select>
+                </div>
             </div>
         </div>
     }
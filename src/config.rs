@@ -0,0 +1,179 @@
+//! Serializable definitions of options, groups and selections, available with the `serde` feature.
+//!
+//! A [`SelectConfig`] describes the options, groups and limits of a select, e.g. loaded as JSON from a backend,
+//! and a [`SelectionState`] its current selection.
+
+use crate::locale::SelectLocale;
+use serde::{Deserialize, Serialize};
+
+/// An option of a select.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionConfig {
+    /// The value of the option, reported when it is selected.
+    pub value: String,
+    /// The label displayed for the option.
+    pub label: String,
+    /// Whether the option cannot be selected.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub disabled: bool,
+    /// The label of the group the option belongs to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// A group of options of a select, referenced by the `group` of its options.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupConfig {
+    /// The label of the group.
+    pub label: String,
+    /// Whether the group can be collapsed in custom mode.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub collapsible: bool,
+    /// Whether a collapsible group starts collapsed.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub default_collapsed: bool,
+}
+
+/// The values selected in a select.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectionState {
+    /// The selected values, in selection order.
+    pub values: Vec<String>,
+}
+
+impl From<Vec<String>> for SelectionState {
+    fn from(values: Vec<String>) -> Self {
+        Self { values }
+    }
+}
+
+/// The configuration of a select: its options and groups, how many values can be selected and its
+/// initial selection.
+///
+/// Every field may be omitted when deserializing.
+///
+/// # Example
+/// ```rust
+/// use selectrs::config::SelectConfig;
+///
+/// let config: SelectConfig = serde_json::from_str(r#"{
+///     "name": "fruits",
+///     "placeholder": "Pick fruits...",
+///     "multiple": true,
+///     "max_selected": 2,
+///     "groups": [{ "label": "Berries", "collapsible": true }],
+///     "options": [
+///         { "value": "apple", "label": "Apple" },
+///         { "value": "strawberry", "label": "Strawberry", "group": "Berries" },
+///         { "value": "durian", "label": "Durian", "disabled": true }
+///     ],
+///     "selection": { "values": ["apple"] }
+/// }"#).unwrap();
+///
+/// let groups = config.grouped_options();
+/// assert_eq!(groups[0].0.map(|group| group.label.as_str()), Some("Berries"));
+/// assert_eq!(groups[1].1.iter().map(|option| option.value.as_str()).collect::<Vec<_>>(), ["apple", "durian"]);
+/// assert!(config.validate(&config.selection.values, &Default::default()).is_ok());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelectConfig {
+    /// The name of the select, used in forms and to persist its selection.
+    pub name: String,
+    /// The id of the select.
+    pub id: String,
+    /// The text displayed while nothing is selected.
+    pub placeholder: String,
+    /// Whether several values can be selected.
+    pub multiple: bool,
+    /// Whether to render the custom popup list instead of the native `select`.
+    pub custom: bool,
+    /// Whether the options can be searched, in custom mode.
+    pub searchable: bool,
+    /// Whether the select is disabled.
+    pub disabled: bool,
+    /// The minimum number of selected values, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_selected: Option<usize>,
+    /// The maximum number of selected values, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_selected: Option<usize>,
+    /// The maximum number of chips displayed before collapsing the others, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_visible_chips: Option<usize>,
+    /// The groups of the options, in display order. Groups referenced by options but missing here are listed
+    /// after them, in order of appearance.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupConfig>,
+    /// The options, in display order within their group.
+    pub options: Vec<OptionConfig>,
+    /// The values selected initially.
+    pub selection: SelectionState,
+}
+
+impl SelectConfig {
+    /// Returns the options by group, in display order, with the options without a group last.
+    pub fn grouped_options(&self) -> Vec<(Option<&GroupConfig>, Vec<&OptionConfig>)> {
+        let mut labels: Vec<&str> = self
+            .groups
+            .iter()
+            .map(|group| group.label.as_str())
+            .collect();
+        for option in &self.options {
+            if let Some(group) = option.group.as_deref() {
+                if !labels.contains(&group) {
+                    labels.push(group);
+                }
+            }
+        }
+        let mut grouped: Vec<(Option<&GroupConfig>, Vec<&OptionConfig>)> = labels
+            .into_iter()
+            .map(|label| {
+                let config = self.groups.iter().find(|group| group.label == label);
+                let options: Vec<&OptionConfig> = self
+                    .options
+                    .iter()
+                    .filter(|option| option.group.as_deref() == Some(label))
+                    .collect();
+                (config, options)
+            })
+            .filter(|(_, options)| !options.is_empty())
+            .collect();
+        let ungrouped: Vec<&OptionConfig> = self
+            .options
+            .iter()
+            .filter(|option| option.group.is_none())
+            .collect();
+        if !ungrouped.is_empty() {
+            grouped.push((None, ungrouped));
+        }
+        grouped
+    }
+
+    /// Checks that the number of `values` respects `min_selected` and `max_selected`, returning the localized
+    /// error message otherwise.
+    ///
+    /// # Example
+    /// ```rust
+    /// use selectrs::config::SelectConfig;
+    /// use selectrs::locale::SelectLocale;
+    ///
+    /// let config = SelectConfig { min_selected: Some(1), max_selected: Some(2), ..SelectConfig::default() };
+    /// let locale = SelectLocale::default();
+    /// let values = |count| vec![String::new(); count];
+    /// assert_eq!(config.validate(&values(0), &locale), Err("Select at least 1 option(s)".to_string()));
+    /// assert_eq!(config.validate(&values(2), &locale), Ok(()));
+    /// assert_eq!(config.validate(&values(3), &locale), Err("Select at most 2 option(s)".to_string()));
+    /// ```
+    pub fn validate(&self, values: &[String], locale: &SelectLocale) -> Result<(), String> {
+        match (self.min_selected, self.max_selected) {
+            (Some(min), _) if values.len() < min => Err(SelectLocale::fill(locale.too_few, min)),
+            (_, Some(max)) if values.len() > max => Err(SelectLocale::fill(locale.too_many, max)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
@@ -0,0 +1,313 @@
+//! Fuzzy matching of search queries against option labels.
+//!
+//! The characters of a query are found in order, but not necessarily next to each other, e.g. `"nyc"` in
+//! `"New York City"`. Matches at the start of the text or of a word and consecutive matches score higher,
+//! while gaps between matched characters cost a little.
+
+use std::cmp::Reverse;
+use std::ops::Range;
+
+/// Score of every matched character.
+const SCORE_MATCH: i64 = 16;
+/// Bonus of a character matched at the start of the text.
+const BONUS_PREFIX: i64 = 12;
+/// Bonus of a character matched at the start of a word, e.g. after a space or at a camel case hump.
+const BONUS_BOUNDARY: i64 = 10;
+/// Bonus of a character matched right after the previously matched one.
+const BONUS_CONSECUTIVE: i64 = 8;
+/// Penalty of a gap between two matched characters, on top of one point per skipped character.
+const PENALTY_GAP: i64 = 2;
+
+/// A successful fuzzy match of a query in a text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// The score of the match. Higher scores are better matches; an empty query scores `0`.
+    pub score: i64,
+    /// The byte ranges of the matched characters in the text, merged when adjacent and in increasing order.
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl FuzzyMatch {
+    /// Splits `text` into consecutive segments, each flagged with whether it was matched.
+    ///
+    /// `text` must be the text the match was computed for.
+    ///
+    /// # Example
+    /// ```rust
+    /// use selectrs::fuzzy::fuzzy_match;
+    ///
+    /// let text = "New York City";
+    /// let found = fuzzy_match("nyc", text).unwrap();
+    /// assert_eq!(
+    ///     found.segments(text),
+    ///     [("N", true), ("ew ", false), ("Y", true), ("ork ", false), ("C", true), ("ity", false)]
+    /// );
+    /// ```
+    pub fn segments<'a>(&self, text: &'a str) -> Vec<(&'a str, bool)> {
+        let mut segments = Vec::new();
+        let mut end = 0;
+        for range in &self.ranges {
+            if range.start > end {
+                segments.push((&text[end..range.start], false));
+            }
+            segments.push((&text[range.clone()], true));
+            end = range.end;
+        }
+        if end < text.len() {
+            segments.push((&text[end..], false));
+        }
+        segments
+    }
+}
+
+/// Matches `query` against `text`, ignoring case and the whitespace of the query.
+///
+/// Returns `None` if the characters of the query do not all appear in the text in the same order, and the
+/// best scoring match otherwise. See [`fuzzy_match_with`] to ignore more differences, such as accents.
+///
+/// # Example
+/// ```rust
+/// use selectrs::fuzzy::fuzzy_match;
+///
+/// let found = fuzzy_match("nyc", "New York City").unwrap();
+/// assert_eq!(found.ranges, [0..1, 4..5, 9..10]);
+/// assert!(fuzzy_match("cyn", "New York City").is_none());
+///
+/// // Matches at the start of the text or of a word score higher than matches inside a word.
+/// let score = |query, text| fuzzy_match(query, text).unwrap().score;
+/// assert!(score("ap", "Apple") > score("ap", "Grape"));
+/// assert!(score("gb", "Great Britain") > score("gb", "Rugby"));
+/// assert!(score("york", "New York") > score("york", "Yellow Fork"));
+///
+/// // Ranges are byte ranges, so they can slice texts with multibyte characters.
+/// let text = "Zürich Öst";
+/// let found = fuzzy_match("ZÖ", text).unwrap();
+/// assert_eq!(found.ranges.iter().map(|range| &text[range.clone()]).collect::<Vec<_>>(), ["Z", "Ö"]);
+/// ```
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    fuzzy_match_with(query, text, |c| c.to_lowercase().collect())
+}
+
+/// Matches `query` against `text` after normalizing every character of both with `normalize`.
+///
+/// A character may normalize to several characters or none. The ranges of the match still refer to the
+/// characters of the original text, so a match in the normalized form of `é` highlights the whole `é`.
+///
+/// # Example
+/// ```rust
+/// use selectrs::fuzzy::fuzzy_match_with;
+/// use selectrs::locale::SelectLocale;
+/// use selectrs::normalize::Normalization;
+///
+/// let locale = SelectLocale::default();
+/// let normalize = |c| Normalization::default().normalize_char(c, &locale);
+///
+/// let text = "Crème brûlée";
+/// let found = fuzzy_match_with("creme", text, normalize).unwrap();
+/// assert_eq!(&text[found.ranges[0].clone()], "Crème");
+/// assert!(fuzzy_match_with("muller", "Müller", normalize).is_some());
+/// assert!(fuzzy_match_with("strasse", "Straße", normalize).is_some());
+/// ```
+pub fn fuzzy_match_with(
+    query: &str,
+    text: &str,
+    normalize: impl Fn(char) -> String,
+) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .flat_map(|c| normalize(c).chars().collect::<Vec<_>>())
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+    // Every normalized character with the byte offset and the original character it comes from.
+    let chars: Vec<(usize, char, char)> = text
+        .char_indices()
+        .flat_map(|(offset, original)| {
+            normalize(original)
+                .chars()
+                .map(|c| (offset, original, c))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let (m, n) = (query.len(), chars.len());
+    if m > n {
+        return None;
+    }
+
+    // `scores[i][j]` is the best score of the query up to `query[i]`, with `query[i]` matched at `chars[j]`,
+    // and `previous[i][j]` the position of `query[i - 1]` in that alignment.
+    let mut scores = vec![vec![None::<i64>; n]; m];
+    let mut previous = vec![vec![0; n]; m];
+    for i in 0..m {
+        // The best alignment of the previous query character with a gap before `j`, as its score plus its
+        // position, so that the gap penalty can be applied once the position of the gap end is known.
+        let mut best_gap = None::<(i64, usize)>;
+        for j in i..n {
+            if i > 0 && j >= 2 {
+                if let Some(score) = scores[i - 1][j - 2] {
+                    let candidate = score + (j - 2) as i64;
+                    if !best_gap.is_some_and(|(best, _)| best >= candidate) {
+                        best_gap = Some((candidate, j - 2));
+                    }
+                }
+            }
+            if chars[j].2 != query[i] {
+                continue;
+            }
+            let base = SCORE_MATCH + bonus(&chars, j);
+            if i == 0 {
+                scores[i][j] = Some(base);
+                continue;
+            }
+            let consecutive = scores[i - 1][j - 1].map(|score| (score + BONUS_CONSECUTIVE, j - 1));
+            let gap = best_gap.map(|(score, k)| (score - (j - 1) as i64 - PENALTY_GAP, k));
+            if let Some((score, k)) = consecutive
+                .into_iter()
+                .chain(gap)
+                .max_by_key(|&(score, _)| score)
+            {
+                scores[i][j] = Some(base + score);
+                previous[i][j] = k;
+            }
+        }
+    }
+
+    let (end, score) = (0..n)
+        .filter_map(|j| scores[m - 1][j].map(|score| (j, score)))
+        .max_by_key(|&(j, score)| (score, Reverse(j)))?;
+
+    let mut positions = vec![end; m];
+    for i in (1..m).rev() {
+        positions[i - 1] = previous[i][positions[i]];
+    }
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for j in positions {
+        let (start, original, _) = chars[j];
+        let end = start + original.len_utf8();
+        match ranges.last_mut() {
+            // Several normalized characters may come from the same original one.
+            Some(range) if range.end >= start => range.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+    Some(FuzzyMatch { score, ranges })
+}
+
+/// Returns the bonus of a character matched at position `j` of `chars`, judged on the original characters.
+fn bonus(chars: &[(usize, char, char)], j: usize) -> i64 {
+    let Some(&(offset, before, _)) = j.checked_sub(1).and_then(|i| chars.get(i)) else {
+        return BONUS_PREFIX;
+    };
+    let (start, c, _) = chars[j];
+    if offset == start {
+        return 0;
+    }
+    let word_start = !before.is_alphanumeric() && c.is_alphanumeric();
+    let hump = before.is_lowercase() && c.is_uppercase();
+    let digits = before.is_alphabetic() && c.is_numeric();
+    if word_start || hump || digits {
+        BONUS_BOUNDARY
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(query: &str, text: &str) -> i64 {
+        fuzzy_match(query, text).unwrap().score
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(fuzzy_match("", "Apple"), Some(FuzzyMatch::default()));
+        assert_eq!(fuzzy_match("  ", ""), Some(FuzzyMatch::default()));
+    }
+
+    #[test]
+    fn missing_or_reordered_characters_do_not_match() {
+        assert_eq!(fuzzy_match("x", "Apple"), None);
+        assert_eq!(fuzzy_match("pa", "Apple"), None);
+        assert_eq!(fuzzy_match("apples", "Apple"), None);
+        assert_eq!(fuzzy_match("a", ""), None);
+    }
+
+    #[test]
+    fn query_whitespace_and_case_are_ignored() {
+        let found = fuzzy_match("N Y C", "new york city").unwrap();
+        assert_eq!(found.ranges, [0..1, 4..5, 9..10]);
+    }
+
+    #[test]
+    fn prefix_beats_word_start_beats_inner_match() {
+        assert!(score("c", "Cherry") > score("c", "Ice cream"));
+        assert!(score("c", "Ice cream") > score("c", "Ice"));
+    }
+
+    #[test]
+    fn consecutive_matches_beat_gaps() {
+        assert!(score("ab", "abxxx") > score("ab", "axxbx"));
+    }
+
+    #[test]
+    fn longer_gaps_cost_more() {
+        assert!(score("ab", "axb") > score("ab", "axxb"));
+        assert!(score("ab", "axxb") > score("ab", "axxxxb"));
+        assert_eq!(
+            score("ab", "axb") - score("ab", "axxb"),
+            1,
+            "every skipped character costs one point"
+        );
+    }
+
+    #[test]
+    fn best_alignment_is_chosen() {
+        // Matching the first `a` and `b` leaves a gap, the consecutive `ab` at a word start scores higher.
+        let found = fuzzy_match("ab", "xa b ab").unwrap();
+        assert_eq!(found.ranges, vec![5..7]);
+    }
+
+    #[test]
+    fn camel_case_humps_are_word_starts() {
+        let found = fuzzy_match("gh", "GitHub").unwrap();
+        assert_eq!(found.ranges, [0..1, 3..4]);
+    }
+
+    #[test]
+    fn non_ascii_ranges_are_byte_ranges() {
+        let text = "Ünïcödé straße";
+        let found = fuzzy_match("ücß", text).unwrap();
+        let matched: Vec<&str> = found
+            .ranges
+            .iter()
+            .map(|range| &text[range.clone()])
+            .collect();
+        assert_eq!(matched, ["Ü", "c", "ß"]);
+        assert_eq!(
+            found.segments(text),
+            [
+                ("Ü", true),
+                ("nï", false),
+                ("c", true),
+                ("ödé stra", false),
+                ("ß", true),
+                ("e", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn expanded_characters_highlight_the_original() {
+        let normalize = |c: char| match c {
+            'ß' => "ss".to_string(),
+            c => c.to_lowercase().collect(),
+        };
+        let text = "Straße";
+        let found = fuzzy_match_with("strasse", text, normalize).unwrap();
+        assert_eq!(found.ranges, vec![0..text.len()]);
+    }
+}
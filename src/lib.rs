@@ -5,5 +5,18 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "serde")]
+pub mod config;
+#[cfg(feature = "normalize")]
+pub mod fuzzy;
+#[cfg(feature = "normalize")]
+pub mod locale;
+#[cfg(feature = "normalize")]
+pub mod normalize;
+pub mod popup;
+pub mod query;
+pub mod storage;
+#[cfg(feature = "theme")]
+pub mod theme;
 #[cfg(feature = "yew")]
 pub mod yew;
@@ -0,0 +1,303 @@
+//! Localization of the built-in strings and locale-aware text handling.
+//!
+//! [`SelectLocale`] holds the strings rendered by the components and the language used to fold case and sort
+//! option labels.
+
+use std::cmp::Ordering;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// The built-in strings of the select components and the language used to sort and match labels.
+///
+/// Templates contain a `{}` placeholder, which is replaced by [`SelectLocale::fill`]. Every field can be
+/// overridden with the struct update syntax, starting from the English defaults.
+///
+/// # Example
+/// ```rust
+/// use selectrs::locale::SelectLocale;
+///
+/// let locale = SelectLocale {
+///     language: "de",
+///     no_results: "Keine Ergebnisse",
+///     remove: "{} entfernen",
+///     ..SelectLocale::default()
+/// };
+/// assert_eq!(SelectLocale::fill(locale.remove, "Apfel"), "Apfel entfernen");
+/// assert_eq!(locale.clear, "Clear selection");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectLocale {
+    /// The BCP 47 language tag, e.g. `"en"` or `"tr-TR"`, selecting the case mapping and collation rules.
+    pub language: &'static str,
+    /// Accessible label of the button removing a chip, with the chip label as placeholder.
+    pub remove: &'static str,
+    /// Accessible label of the button clearing the selection.
+    pub clear: &'static str,
+    /// Placeholder of the search input.
+    pub search_placeholder: &'static str,
+    /// Message shown when no option matches the search or no option is available.
+    pub no_results: &'static str,
+    /// Label of the entry creating a new option, with the typed text as placeholder.
+    pub create: &'static str,
+    /// Message shown while options are being loaded.
+    pub loading: &'static str,
+    /// Summary of the selection, with the number of selected values as placeholder.
+    pub selected_count: &'static str,
+    /// Label of the chip standing for hidden chips, with their number as placeholder.
+    pub more: &'static str,
+    /// Label of the chip collapsing the chips again once they are expanded.
+    pub show_less: &'static str,
+    /// Label of the section listing the pinned options.
+    pub pinned: &'static str,
+    /// Label of the section listing the recently chosen options.
+    pub recent: &'static str,
+    /// Error shown when too few values are selected, with the minimum as placeholder.
+    pub too_few: &'static str,
+    /// Error shown when too many values are selected, with the maximum as placeholder.
+    pub too_many: &'static str,
+}
+
+impl Default for SelectLocale {
+    fn default() -> Self {
+        Self {
+            language: "en",
+            remove: "Remove {}",
+            clear: "Clear selection",
+            search_placeholder: "Search…",
+            no_results: "No results",
+            create: "Create \"{}\"",
+            loading: "Loading…",
+            selected_count: "{} selected",
+            more: "+{} more",
+            show_less: "Show less",
+            pinned: "Pinned",
+            recent: "Recent",
+            too_few: "Select at least {} option(s)",
+            too_many: "Select at most {} option(s)",
+        }
+    }
+}
+
+impl SelectLocale {
+    /// Replaces the `{}` placeholder of `template` by `value`.
+    pub fn fill(template: &str, value: impl std::fmt::Display) -> String {
+        template.replacen("{}", &value.to_string(), 1)
+    }
+
+    /// Returns the primary language subtag, lowercased, e.g. `"tr"` for `"tr-TR"`.
+    fn primary_language(&self) -> String {
+        self.language
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+    }
+
+    /// Returns `text` with its case folded according to the language, for case-insensitive comparisons.
+    ///
+    /// Besides the Unicode lowercase mapping, this applies the full case folding of characters such as `ß`
+    /// and final sigma, and the Turkish and Azerbaijani rules for the dotted and dotless I.
+    ///
+    /// # Example
+    /// ```rust
+    /// use selectrs::locale::SelectLocale;
+    ///
+    /// let english = SelectLocale::default();
+    /// let turkish = SelectLocale { language: "tr", ..SelectLocale::default() };
+    ///
+    /// assert_eq!(english.fold_case("STRASSE"), english.fold_case("Straße"));
+    /// assert_eq!(english.fold_case("ΟΔΟΣ"), "οδοσ");
+    /// assert_eq!(english.fold_case("DIŞ"), "diş");
+    /// assert_eq!(turkish.fold_case("DIŞ"), "dış");
+    /// assert_eq!(turkish.fold_case("İstanbul"), "istanbul");
+    /// ```
+    pub fn fold_case(&self, text: &str) -> String {
+        let turkic = matches!(self.primary_language().as_str(), "tr" | "az");
+        let mut folded = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                'I' if turkic => folded.push('ı'),
+                'İ' if turkic => folded.push('i'),
+                // Without the Turkic rules, the dotted capital I folds to `i` followed by a combining dot.
+                'İ' => folded.push_str("i\u{307}"),
+                'ß' | 'ẞ' => folded.push_str("ss"),
+                'ς' => folded.push('σ'),
+                _ => folded.extend(c.to_lowercase()),
+            }
+        }
+        folded
+    }
+
+    /// Returns whether `text` contains `query`, ignoring case according to the language.
+    ///
+    /// # Example
+    /// ```rust
+    /// use selectrs::locale::SelectLocale;
+    ///
+    /// let turkish = SelectLocale { language: "tr", ..SelectLocale::default() };
+    /// assert!(turkish.matches("İZMİR", "izmir"));
+    /// assert!(!turkish.matches("IRMAK", "irmak"));
+    /// assert!(turkish.matches("IRMAK", "ırmak"));
+    /// assert!(SelectLocale::default().matches("Große Straße", "strasse"));
+    /// ```
+    pub fn matches(&self, text: &str, query: &str) -> bool {
+        self.fold_case(text).contains(&self.fold_case(query))
+    }
+
+    /// Compares two labels according to the alphabet of the language.
+    ///
+    /// Labels are compared letter by letter while ignoring accents and case, so that `"école"` sorts next to
+    /// `"ecole"` rather than after `"z"`. Accents and then case only break ties. Letters that are distinct in
+    /// the language keep their own place in its alphabet, e.g. `ç`, `ğ`, `ı`, `ö`, `ş` and `ü` in Turkish,
+    /// `å`, `ä` and `ö` in Swedish and Finnish, `æ`, `ø` and `å` in Danish and Norwegian, and `ñ` in Spanish.
+    ///
+    /// # Example
+    /// ```rust
+    /// use selectrs::locale::SelectLocale;
+    ///
+    /// let mut labels = vec!["zebra", "Émile", "apple", "Eagle", "éclair"];
+    /// let english = SelectLocale::default();
+    /// labels.sort_by(|a, b| english.compare(a, b));
+    /// assert_eq!(labels, ["apple", "Eagle", "éclair", "Émile", "zebra"]);
+    ///
+    /// let mut labels = vec!["ördek", "zeytin", "ılık", "inek", "çay", "ceviz"];
+    /// let turkish = SelectLocale { language: "tr", ..SelectLocale::default() };
+    /// labels.sort_by(|a, b| turkish.compare(a, b));
+    /// assert_eq!(labels, ["ceviz", "çay", "ılık", "inek", "ördek", "zeytin"]);
+    ///
+    /// let mut labels = vec!["ö", "z", "å", "a", "ä"];
+    /// let swedish = SelectLocale { language: "sv", ..SelectLocale::default() };
+    /// labels.sort_by(|a, b| swedish.compare(a, b));
+    /// assert_eq!(labels, ["a", "z", "å", "ä", "ö"]);
+    /// ```
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        let language = self.primary_language();
+        let (a_primary, a_secondary) = self.collation_keys(&language, a);
+        let (b_primary, b_secondary) = self.collation_keys(&language, b);
+        a_primary
+            .cmp(&b_primary)
+            .then_with(|| a_secondary.cmp(&b_secondary))
+            // Lowercase sorts before uppercase, as in most alphabets.
+            .then_with(|| case_key(a).cmp(&case_key(b)))
+            .then_with(|| a.cmp(b))
+    }
+
+    /// Returns the primary keys (letters of the alphabet) and secondary keys (accents) of `text`.
+    fn collation_keys(&self, language: &str, text: &str) -> (Vec<(char, u8)>, Vec<char>) {
+        let mut primary = Vec::new();
+        let mut secondary = Vec::new();
+        for c in self.fold_case(text).nfc() {
+            if let Some(key) = tailoring(language, c) {
+                primary.push(key);
+                continue;
+            }
+            for d in c.to_string().nfd() {
+                if is_combining_mark(d) {
+                    secondary.push(d);
+                } else {
+                    primary.push((d, 0));
+                }
+            }
+        }
+        (primary, secondary)
+    }
+}
+
+/// Returns the place of `c` in the alphabet of `language` if it is a letter of its own there, as the
+/// letter it follows and its rank after it.
+fn tailoring(language: &str, c: char) -> Option<(char, u8)> {
+    let key = match (language, c) {
+        ("tr" | "az", 'ç') => ('c', 1),
+        ("tr" | "az", 'ğ') => ('g', 1),
+        // The dotless ı precedes the dotted i.
+        ("tr" | "az", 'ı') => ('i', 0),
+        ("tr" | "az", 'i') => ('i', 1),
+        ("tr" | "az", 'ö') => ('o', 1),
+        ("tr" | "az", 'ş') => ('s', 1),
+        ("tr" | "az", 'ü') => ('u', 1),
+        ("sv" | "fi", 'å') => ('z', 1),
+        ("sv" | "fi", 'ä') => ('z', 2),
+        ("sv" | "fi", 'ö') => ('z', 3),
+        ("da" | "nb" | "nn" | "no", 'æ') => ('z', 1),
+        ("da" | "nb" | "nn" | "no", 'ø') => ('z', 2),
+        ("da" | "nb" | "nn" | "no", 'å') => ('z', 3),
+        ("es", 'ñ') => ('n', 1),
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Returns a key ordering lowercase letters before uppercase ones.
+fn case_key(text: &str) -> Vec<bool> {
+    text.chars().map(char::is_uppercase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(language: &'static str) -> SelectLocale {
+        SelectLocale {
+            language,
+            ..SelectLocale::default()
+        }
+    }
+
+    #[test]
+    fn turkish_dotted_and_dotless_i() {
+        let turkish = locale("tr");
+        assert_eq!(turkish.fold_case("İ"), "i");
+        assert_eq!(turkish.fold_case("I"), "ı");
+        assert_eq!(turkish.fold_case("ı"), "ı");
+        assert_eq!(turkish.fold_case("ISPARTA İZMİR"), "ısparta izmir");
+        assert_ne!(turkish.fold_case("I"), turkish.fold_case("i"));
+    }
+
+    #[test]
+    fn turkic_rules_follow_the_primary_language() {
+        assert_eq!(locale("tr-TR").fold_case("I"), "ı");
+        assert_eq!(locale("TR_tr").fold_case("I"), "ı");
+        assert_eq!(locale("az").fold_case("İ"), "i");
+        assert_eq!(locale("tk").fold_case("I"), "i");
+    }
+
+    #[test]
+    fn dotted_i_outside_turkic_keeps_its_dot() {
+        let english = locale("en");
+        assert_eq!(english.fold_case("İ"), "i\u{307}");
+        assert_eq!(english.fold_case("I"), "i");
+        assert!(!english.matches("İstanbul", "istanbul"));
+    }
+
+    #[test]
+    fn full_case_folding() {
+        let english = locale("en");
+        assert_eq!(english.fold_case("ẞ"), "ss");
+        assert_eq!(english.fold_case("ς"), english.fold_case("Σ"));
+    }
+
+    #[test]
+    fn turkish_collation() {
+        let turkish = locale("tr");
+        assert_eq!(turkish.compare("ılık", "inek"), Ordering::Less);
+        assert_eq!(turkish.compare("ceviz", "çay"), Ordering::Less);
+        assert_eq!(turkish.compare("çay", "dere"), Ordering::Less);
+        // Without the Turkish alphabet, `ç` sorts as an accented `c`.
+        assert_eq!(locale("en").compare("çay", "ceviz"), Ordering::Less);
+    }
+
+    #[test]
+    fn accents_and_case_only_break_ties() {
+        let english = locale("en");
+        assert_eq!(english.compare("ecole", "école"), Ordering::Less);
+        assert_eq!(english.compare("école", "ecolf"), Ordering::Less);
+        assert_eq!(english.compare("apple", "Apple"), Ordering::Less);
+        assert_eq!(english.compare("Apple", "Apple"), Ordering::Equal);
+    }
+
+    #[test]
+    fn fill_replaces_the_first_placeholder() {
+        assert_eq!(SelectLocale::fill("{} of {}", 3), "3 of {}");
+        assert_eq!(SelectLocale::fill("none", 3), "none");
+    }
+}
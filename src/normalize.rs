@@ -0,0 +1,278 @@
+//! Normalization of text before searching.
+//!
+//! A [`Normalization`] folds case, decomposes, strips accents and optionally transliterates labels and queries,
+//! so that `"creme"` finds `"Crème brûlée"`. It works character by character, so matches can be mapped back to
+//! the original text.
+
+use crate::locale::SelectLocale;
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+
+/// The steps applied to text before searching.
+///
+/// The enabled steps run in the following order on every character: case folding according to the language
+/// of the locale, Unicode compatibility decomposition (NFKD), stripping of combining marks such as accents,
+/// and transliteration of letters without a decomposition, such as `ø` or Cyrillic letters, to ASCII.
+///
+/// # Example
+/// ```rust
+/// use selectrs::locale::SelectLocale;
+/// use selectrs::normalize::Normalization;
+///
+/// let locale = SelectLocale::default();
+/// let normalization = Normalization::default();
+/// assert_eq!(normalization.normalize("Crème Brûlée", &locale), "creme brulee");
+/// assert_eq!(normalization.normalize("Müller", &locale), "muller");
+/// assert_eq!(normalization.normalize("ﬁle №5", &locale), "file no5");
+///
+/// let transliterating = Normalization { transliterate: true, ..Normalization::default() };
+/// assert_eq!(transliterating.normalize("Søren Łukasz", &locale), "soren lukasz");
+/// assert_eq!(transliterating.normalize("Москва", &locale), "moskva");
+///
+/// let exact = Normalization::NONE;
+/// assert_eq!(exact.normalize("Crème", &locale), "Crème");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Normalization {
+    /// Whether to fold case with [`SelectLocale::fold_case`], so that `"É"` matches `"é"`.
+    pub fold_case: bool,
+    /// Whether to apply the Unicode compatibility decomposition (NFKD), splitting accented letters into a base
+    /// letter and combining marks, and ligatures or styled characters such as `ﬁ` or `①` into plain ones.
+    pub decompose: bool,
+    /// Whether to remove combining marks, so that `"é"` decomposed into `"e"` and an accent matches `"e"`.
+    pub strip_marks: bool,
+    /// Whether to transliterate letters without a decomposition to ASCII, such as `ø`, `ł`, `æ` or `þ`, and
+    /// Greek and Cyrillic letters.
+    pub transliterate: bool,
+}
+
+impl Default for Normalization {
+    /// Folds case, decomposes and strips marks, without transliteration.
+    fn default() -> Self {
+        Self {
+            fold_case: true,
+            decompose: true,
+            strip_marks: true,
+            transliterate: false,
+        }
+    }
+}
+
+impl Normalization {
+    /// Leaves text unchanged, for exact matching.
+    pub const NONE: Self = Self {
+        fold_case: false,
+        decompose: false,
+        strip_marks: false,
+        transliterate: false,
+    };
+
+    /// Returns the normalized form of `text`.
+    pub fn normalize(&self, text: &str, locale: &SelectLocale) -> String {
+        text.chars()
+            .map(|c| self.normalize_char(c, locale))
+            .collect()
+    }
+
+    /// Returns the normalized form of the single character `c`, which may be empty or longer than one character.
+    pub fn normalize_char(&self, c: char, locale: &SelectLocale) -> String {
+        let mut buffer = [0; 4];
+        let folded = if self.fold_case {
+            locale.fold_case(c.encode_utf8(&mut buffer))
+        } else {
+            c.to_string()
+        };
+
+        let mut decomposed = String::with_capacity(folded.len());
+        for c in folded.chars() {
+            if self.decompose {
+                decompose_compatible(c, |c| decomposed.push(c));
+            } else {
+                decomposed.push(c);
+            }
+        }
+        // Compatibility decompositions may introduce uppercase letters, e.g. `№` decomposes into `No`.
+        if self.fold_case && self.decompose {
+            decomposed = locale.fold_case(&decomposed);
+        }
+
+        let mut normalized = String::with_capacity(decomposed.len());
+        for c in decomposed.chars() {
+            if self.strip_marks && is_combining_mark(c) {
+                continue;
+            }
+            match transliteration(c).filter(|_| self.transliterate) {
+                Some(ascii) => normalized.push_str(ascii),
+                None => normalized.push(c),
+            }
+        }
+        normalized
+    }
+}
+
+/// Returns the ASCII transliteration of a lowercase letter without a decomposition, if any.
+fn transliteration(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        // Latin letters
+        'æ' => "ae",
+        'œ' => "oe",
+        'ø' => "o",
+        'ł' => "l",
+        'đ' | 'ð' => "d",
+        'þ' => "th",
+        'ı' => "i",
+        'ħ' => "h",
+        'ŧ' => "t",
+        'ŋ' => "ng",
+        'ß' => "ss",
+        // Greek letters
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' => "i",
+        'θ' => "th",
+        'ι' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' => "o",
+        // Cyrillic letters
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'э' | 'є' => "e",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'й' | 'ы' => "y",
+        'ї' => "yi",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(normalization: Normalization, text: &str) -> String {
+        normalization.normalize(text, &SelectLocale::default())
+    }
+
+    #[test]
+    fn decomposition_and_mark_stripping() {
+        let default = Normalization::default();
+        assert_eq!(normalize(default, "Ångström"), "angstrom");
+        assert_eq!(normalize(default, "Dvořák"), "dvorak");
+        assert_eq!(
+            normalize(default, "Pokémon"),
+            normalize(default, "Poke\u{301}mon")
+        );
+    }
+
+    #[test]
+    fn compatibility_decomposition() {
+        let default = Normalization::default();
+        assert_eq!(normalize(default, "ﬀ"), "ff");
+        assert_eq!(normalize(default, "①"), "1");
+        assert_eq!(normalize(default, "ＡＢＣ"), "abc");
+        assert_eq!(normalize(default, "x²"), "x2");
+    }
+
+    #[test]
+    fn marks_are_kept_without_stripping() {
+        let keep_marks = Normalization {
+            strip_marks: false,
+            ..Normalization::default()
+        };
+        assert_eq!(normalize(keep_marks, "é"), "e\u{301}");
+
+        let compose_only = Normalization {
+            decompose: false,
+            ..Normalization::default()
+        };
+        assert_eq!(normalize(compose_only, "É"), "é");
+    }
+
+    #[test]
+    fn transliteration() {
+        let transliterating = Normalization {
+            transliterate: true,
+            ..Normalization::default()
+        };
+        assert_eq!(normalize(transliterating, "Æsir Þór"), "aesir thor");
+        assert_eq!(normalize(transliterating, "Ελλάδα"), "ellada");
+        assert_eq!(normalize(transliterating, "Щука"), "shchuka");
+        assert_eq!(normalize(transliterating, "Объект"), "obekt");
+        // Letters without a transliteration are kept.
+        assert_eq!(normalize(transliterating, "東京"), "東京");
+        // Without transliteration, such letters are kept too.
+        assert_eq!(normalize(Normalization::default(), "Søren"), "søren");
+    }
+
+    #[test]
+    fn case_folding_uses_the_locale() {
+        let turkish = SelectLocale {
+            language: "tr",
+            ..SelectLocale::default()
+        };
+        let default = Normalization::default();
+        assert_eq!(default.normalize("İZMİR", &turkish), "izmir");
+        assert_eq!(default.normalize("IRMAK", &turkish), "ırmak");
+        // Decomposing the dotted capital I outside Turkish leaves a plain `i` once the dot is stripped.
+        assert_eq!(normalize(default, "İZMİR"), "izmir");
+    }
+
+    #[test]
+    fn none_leaves_text_unchanged() {
+        assert_eq!(normalize(Normalization::NONE, "Crème ﬁ Ø"), "Crème ﬁ Ø");
+    }
+
+    #[test]
+    fn characters_may_normalize_to_nothing() {
+        let transliterating = Normalization {
+            transliterate: true,
+            ..Normalization::default()
+        };
+        let locale = SelectLocale::default();
+        assert_eq!(transliterating.normalize_char('ъ', &locale), "");
+        assert_eq!(
+            Normalization::default().normalize_char('\u{301}', &locale),
+            ""
+        );
+    }
+}
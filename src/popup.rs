@@ -0,0 +1,382 @@
+//! Positioning of floating popups.
+//!
+//! [`compute_position`] places a popup next to its trigger within the viewport, flipping and shifting it when
+//! it would overflow. Rectangles are in viewport coordinates, as returned by `getBoundingClientRect`.
+
+/// A rectangle in viewport coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    /// The distance from the left edge of the viewport.
+    pub x: f64,
+    /// The distance from the top edge of the viewport.
+    pub y: f64,
+    /// The width of the rectangle.
+    pub width: f64,
+    /// The height of the rectangle.
+    pub height: f64,
+}
+
+impl Rect {
+    /// Creates a rectangle from its position and size.
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the distance from the left edge of the viewport to the right edge of the rectangle.
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    /// Returns the distance from the top edge of the viewport to the bottom edge of the rectangle.
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+}
+
+/// The writing direction of the content, which decides which edge is the start edge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// Left to right: the start edge is the left edge.
+    #[default]
+    Ltr,
+    /// Right to left, e.g. for Arabic or Hebrew: the start edge is the right edge.
+    Rtl,
+}
+
+impl Direction {
+    /// Returns whether the direction is right to left.
+    pub fn is_rtl(self) -> bool {
+        self == Direction::Rtl
+    }
+
+    /// Returns the value of the HTML `dir` attribute, `"ltr"` or `"rtl"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// Where a popup is placed relative to its trigger.
+///
+/// The side tells whether the popup opens below or above the trigger, and the alignment whether it
+/// lines up with the start or the end edge of the trigger. Which of the left and right edges is the
+/// start edge depends on the [`Direction`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Below the trigger, aligned with its start edge.
+    #[default]
+    BottomStart,
+    /// Below the trigger, aligned with its end edge.
+    BottomEnd,
+    /// Above the trigger, aligned with its start edge.
+    TopStart,
+    /// Above the trigger, aligned with its end edge.
+    TopEnd,
+}
+
+impl Placement {
+    /// Returns whether the popup opens above the trigger.
+    pub fn is_top(self) -> bool {
+        matches!(self, Placement::TopStart | Placement::TopEnd)
+    }
+
+    /// Returns whether the popup is aligned with the end edge of the trigger.
+    pub fn is_end(self) -> bool {
+        matches!(self, Placement::BottomEnd | Placement::TopEnd)
+    }
+
+    /// Returns the placement on the opposite side of the trigger, keeping the alignment.
+    pub fn flip_side(self) -> Self {
+        Self::from_parts(!self.is_top(), self.is_end())
+    }
+
+    /// Returns the placement aligned with the opposite edge of the trigger, keeping the side.
+    pub fn flip_align(self) -> Self {
+        Self::from_parts(self.is_top(), !self.is_end())
+    }
+
+    /// Returns the kebab-case name of the placement, e.g. `"bottom-start"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Placement::BottomStart => "bottom-start",
+            Placement::BottomEnd => "bottom-end",
+            Placement::TopStart => "top-start",
+            Placement::TopEnd => "top-end",
+        }
+    }
+
+    fn from_parts(top: bool, end: bool) -> Self {
+        match (top, end) {
+            (false, false) => Placement::BottomStart,
+            (false, true) => Placement::BottomEnd,
+            (true, false) => Placement::TopStart,
+            (true, true) => Placement::TopEnd,
+        }
+    }
+}
+
+/// The computed position of a popup.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PopupPosition {
+    /// The distance from the left edge of the viewport to the popup.
+    pub x: f64,
+    /// The distance from the top edge of the viewport to the popup.
+    pub y: f64,
+    /// The space available on the chosen side, to be used as the maximum height of the popup.
+    pub max_height: f64,
+    /// The placement actually used, after flipping.
+    pub placement: Placement,
+}
+
+/// Computes the position of a popup of size `popup` next to `trigger` within `viewport`.
+///
+/// The popup is placed according to `placement`, `offset` pixels away from the trigger. Its start edge is
+/// the left edge in `Direction::Ltr` and the right edge in `Direction::Rtl`. If it does not fit on the
+/// preferred side but there is more room on the other one, it is flipped to that side. The alignment is
+/// flipped the same way when the popup overflows horizontally, and the popup is finally shifted along the
+/// horizontal axis to stay within the viewport.
+///
+/// # Example
+/// ```rust
+/// use selectrs::popup::{compute_position, Direction, Placement, Rect};
+///
+/// let viewport = Rect::new(0.0, 0.0, 800.0, 600.0);
+///
+/// // Enough room below the trigger: the preferred placement is kept.
+/// let trigger = Rect::new(100.0, 100.0, 200.0, 40.0);
+/// let position = compute_position(trigger, Rect::new(0.0, 0.0, 200.0, 300.0), viewport, Placement::BottomStart, Direction::Ltr, 4.0);
+/// assert_eq!(position.placement, Placement::BottomStart);
+/// assert_eq!((position.x, position.y), (100.0, 144.0));
+///
+/// // Too close to the bottom edge: the popup flips above the trigger.
+/// let trigger = Rect::new(100.0, 500.0, 200.0, 40.0);
+/// let position = compute_position(trigger, Rect::new(0.0, 0.0, 200.0, 300.0), viewport, Placement::BottomStart, Direction::Ltr, 4.0);
+/// assert_eq!(position.placement, Placement::TopStart);
+/// assert_eq!(position.y, 196.0);
+///
+/// // Too close to the right edge: the popup is aligned with the end edge of the trigger instead.
+/// let trigger = Rect::new(700.0, 100.0, 80.0, 40.0);
+/// let position = compute_position(trigger, Rect::new(0.0, 0.0, 300.0, 100.0), viewport, Placement::BottomStart, Direction::Ltr, 0.0);
+/// assert_eq!(position.placement, Placement::BottomEnd);
+/// assert_eq!(position.x, 480.0);
+///
+/// // Overflowing with both alignments: the popup is shifted back into the viewport.
+/// let trigger = Rect::new(-50.0, 100.0, 100.0, 40.0);
+/// let position = compute_position(trigger, Rect::new(0.0, 0.0, 300.0, 100.0), viewport, Placement::BottomStart, Direction::Ltr, 0.0);
+/// assert_eq!(position.x, 0.0);
+///
+/// // Right to left: the start edge of the popup lines up with the right edge of the trigger.
+/// let trigger = Rect::new(400.0, 100.0, 200.0, 40.0);
+/// let position = compute_position(trigger, Rect::new(0.0, 0.0, 300.0, 100.0), viewport, Placement::BottomStart, Direction::Rtl, 0.0);
+/// assert_eq!(position.placement, Placement::BottomStart);
+/// assert_eq!(position.x, 300.0);
+/// ```
+pub fn compute_position(
+    trigger: Rect,
+    popup: Rect,
+    viewport: Rect,
+    placement: Placement,
+    direction: Direction,
+    offset: f64,
+) -> PopupPosition {
+    let space_below = viewport.bottom() - trigger.bottom() - offset;
+    let space_above = trigger.y - viewport.y - offset;
+    let (space, other_space) = if placement.is_top() {
+        (space_above, space_below)
+    } else {
+        (space_below, space_above)
+    };
+    let placement = if popup.height > space && other_space > space {
+        placement.flip_side()
+    } else {
+        placement
+    };
+
+    let align_x = |placement: Placement| {
+        if placement.is_end() != direction.is_rtl() {
+            trigger.right() - popup.width
+        } else {
+            trigger.x
+        }
+    };
+    let overflows = |x: f64| x < viewport.x || x + popup.width > viewport.right();
+    let placement = if overflows(align_x(placement)) && !overflows(align_x(placement.flip_align()))
+    {
+        placement.flip_align()
+    } else {
+        placement
+    };
+
+    let x = align_x(placement)
+        .min(viewport.right() - popup.width)
+        .max(viewport.x);
+    let (y, max_height) = if placement.is_top() {
+        (
+            trigger.y - offset - popup.height.min(space_above),
+            space_above,
+        )
+    } else {
+        (trigger.bottom() + offset, space_below)
+    };
+
+    PopupPosition {
+        x,
+        y,
+        max_height: max_height.max(0.0),
+        placement,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEWPORT: Rect = Rect {
+        x: 0.0,
+        y: 0.0,
+        width: 800.0,
+        height: 600.0,
+    };
+
+    fn position(
+        trigger: Rect,
+        popup: (f64, f64),
+        placement: Placement,
+        direction: Direction,
+    ) -> PopupPosition {
+        let popup = Rect::new(0.0, 0.0, popup.0, popup.1);
+        compute_position(trigger, popup, VIEWPORT, placement, direction, 4.0)
+    }
+
+    #[test]
+    fn keeps_the_preferred_side_when_it_fits() {
+        let trigger = Rect::new(100.0, 100.0, 200.0, 40.0);
+        let below = position(
+            trigger,
+            (200.0, 300.0),
+            Placement::BottomStart,
+            Direction::Ltr,
+        );
+        assert_eq!(below.placement, Placement::BottomStart);
+        assert_eq!((below.x, below.y, below.max_height), (100.0, 144.0, 456.0));
+    }
+
+    #[test]
+    fn flips_to_the_side_with_more_room() {
+        let trigger = Rect::new(100.0, 500.0, 200.0, 40.0);
+        let flipped = position(
+            trigger,
+            (200.0, 300.0),
+            Placement::BottomStart,
+            Direction::Ltr,
+        );
+        assert_eq!(flipped.placement, Placement::TopStart);
+        assert_eq!((flipped.y, flipped.max_height), (196.0, 496.0));
+
+        let trigger = Rect::new(100.0, 20.0, 200.0, 40.0);
+        let flipped = position(trigger, (200.0, 300.0), Placement::TopEnd, Direction::Ltr);
+        assert_eq!(flipped.placement, Placement::BottomEnd);
+        assert_eq!(flipped.y, 64.0);
+    }
+
+    #[test]
+    fn stays_when_the_other_side_is_smaller() {
+        // Neither side fits, but there is more room below.
+        let trigger = Rect::new(100.0, 250.0, 200.0, 40.0);
+        let cramped = position(
+            trigger,
+            (200.0, 400.0),
+            Placement::BottomStart,
+            Direction::Ltr,
+        );
+        assert_eq!(cramped.placement, Placement::BottomStart);
+        assert_eq!(cramped.max_height, 306.0);
+    }
+
+    #[test]
+    fn top_placement_is_clamped_to_the_available_space() {
+        let trigger = Rect::new(100.0, 100.0, 200.0, 40.0);
+        let above = position(trigger, (200.0, 300.0), Placement::TopStart, Direction::Ltr);
+        // Flipped below, since there is more room there.
+        assert_eq!(above.placement, Placement::BottomStart);
+
+        let trigger = Rect::new(100.0, 560.0, 200.0, 40.0);
+        let above = position(trigger, (200.0, 600.0), Placement::TopStart, Direction::Ltr);
+        assert_eq!(above.placement, Placement::TopStart);
+        assert_eq!((above.y, above.max_height), (0.0, 556.0));
+    }
+
+    #[test]
+    fn flips_alignment_on_horizontal_overflow() {
+        let trigger = Rect::new(700.0, 100.0, 80.0, 40.0);
+        let flipped = position(
+            trigger,
+            (300.0, 100.0),
+            Placement::BottomStart,
+            Direction::Ltr,
+        );
+        assert_eq!(flipped.placement, Placement::BottomEnd);
+        assert_eq!(flipped.x, 480.0);
+    }
+
+    #[test]
+    fn shifts_into_the_viewport_when_no_alignment_fits() {
+        let trigger = Rect::new(-50.0, 100.0, 100.0, 40.0);
+        let shifted = position(
+            trigger,
+            (300.0, 100.0),
+            Placement::BottomStart,
+            Direction::Ltr,
+        );
+        assert_eq!(shifted.placement, Placement::BottomStart);
+        assert_eq!(shifted.x, 0.0);
+
+        let trigger = Rect::new(750.0, 100.0, 100.0, 40.0);
+        let shifted = position(
+            trigger,
+            (300.0, 100.0),
+            Placement::BottomEnd,
+            Direction::Ltr,
+        );
+        assert_eq!(shifted.x, 500.0);
+    }
+
+    #[test]
+    fn right_to_left_aligns_with_the_right_edge() {
+        let trigger = Rect::new(400.0, 100.0, 200.0, 40.0);
+        let start = position(
+            trigger,
+            (300.0, 100.0),
+            Placement::BottomStart,
+            Direction::Rtl,
+        );
+        assert_eq!((start.placement, start.x), (Placement::BottomStart, 300.0));
+        let end = position(
+            trigger,
+            (300.0, 100.0),
+            Placement::BottomEnd,
+            Direction::Rtl,
+        );
+        assert_eq!((end.placement, end.x), (Placement::BottomEnd, 400.0));
+    }
+
+    #[test]
+    fn right_to_left_flips_alignment_near_the_left_edge() {
+        let trigger = Rect::new(20.0, 100.0, 80.0, 40.0);
+        let flipped = position(
+            trigger,
+            (300.0, 100.0),
+            Placement::BottomStart,
+            Direction::Rtl,
+        );
+        assert_eq!(flipped.placement, Placement::BottomEnd);
+        assert_eq!(flipped.x, 20.0);
+    }
+}
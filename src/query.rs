@@ -0,0 +1,255 @@
+//! Reading and writing selections in URL query strings.
+//!
+//! [`read_query`] and [`write_query`] read and rewrite one parameter of a query string, such as `?tags=rust,yew`
+//! or `?tags=rust&tags=yew`, and leave every other parameter untouched.
+
+/// How the values of a selection are encoded in a query parameter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueryEncoding {
+    /// Repeat the parameter for every value, e.g. `tags=rust&tags=yew`.
+    #[default]
+    Repeated,
+    /// Join the values with commas in a single parameter, e.g. `tags=rust,yew`. Commas inside values are
+    /// percent-encoded, so they survive the round trip.
+    Comma,
+}
+
+/// Returns the values of the parameter `key` in `query`, or `None` if the parameter is missing.
+///
+/// `query` may start with `?`. Keys and values are percent-decoded, with `+` decoding to a space.
+///
+/// # Example
+/// ```rust
+/// use selectrs::query::{read_query, QueryEncoding};
+///
+/// let query = "?page=2&tags=rust&tags=web%20apps";
+/// assert_eq!(read_query(query, "tags", QueryEncoding::Repeated), Some(vec!["rust".into(), "web apps".into()]));
+/// assert_eq!(read_query("?tags=rust,yew", "tags", QueryEncoding::Comma), Some(vec!["rust".into(), "yew".into()]));
+/// assert_eq!(read_query("?tags=", "tags", QueryEncoding::Comma), Some(vec![]));
+/// assert_eq!(read_query(query, "status", QueryEncoding::Repeated), None);
+/// ```
+pub fn read_query(query: &str, key: &str, encoding: QueryEncoding) -> Option<Vec<String>> {
+    let mut found = false;
+    let mut values = Vec::new();
+    for (name, value) in pairs(query).map(split_pair) {
+        if decode(name) != key {
+            continue;
+        }
+        found = true;
+        match encoding {
+            QueryEncoding::Repeated => values.push(decode(value)),
+            QueryEncoding::Comma => values.extend(value.split(',').map(decode)),
+        }
+    }
+    values.retain(|value| !value.is_empty());
+    found.then_some(values)
+}
+
+/// Returns `query` with the parameter `key` set to `values`, keeping every other parameter in place.
+///
+/// The result starts with `?` unless it is empty. The parameter is removed when `values` is empty, and takes
+/// the position of its first occurrence, or the end, otherwise.
+///
+/// # Example
+/// ```rust
+/// use selectrs::query::{write_query, QueryEncoding};
+///
+/// let values = ["rust".to_string(), "web apps".to_string()];
+/// assert_eq!(write_query("?page=2", "tags", &values, QueryEncoding::Repeated), "?page=2&tags=rust&tags=web%20apps");
+/// assert_eq!(write_query("?tags=old&page=2", "tags", &values, QueryEncoding::Comma), "?tags=rust,web%20apps&page=2");
+/// assert_eq!(write_query("?tags=old", "tags", &[], QueryEncoding::Comma), "");
+/// ```
+pub fn write_query(query: &str, key: &str, values: &[String], encoding: QueryEncoding) -> String {
+    let name = encode(key);
+    let parameter: Vec<String> = match encoding {
+        _ if values.is_empty() => Vec::new(),
+        QueryEncoding::Repeated => values
+            .iter()
+            .map(|value| format!("{name}={}", encode(value)))
+            .collect(),
+        QueryEncoding::Comma => {
+            let joined: Vec<String> = values.iter().map(|value| encode(value)).collect();
+            vec![format!("{name}={}", joined.join(","))]
+        }
+    };
+
+    let mut parts = Vec::new();
+    let mut parameter = Some(parameter);
+    for pair in pairs(query) {
+        if decode(split_pair(pair).0) == key {
+            parts.extend(parameter.take().into_iter().flatten());
+        } else {
+            parts.push(pair.to_string());
+        }
+    }
+    parts.extend(parameter.into_iter().flatten());
+
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", parts.join("&"))
+    }
+}
+
+/// Splits `query` into its raw `name=value` pairs, skipping empty pairs.
+fn pairs(query: &str) -> impl Iterator<Item = &str> {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+}
+
+/// Splits a raw pair into its raw name and value, the value being empty for a pair without `=`.
+fn split_pair(pair: &str) -> (&str, &str) {
+    pair.split_once('=').unwrap_or((pair, ""))
+}
+
+/// Percent-encodes every byte of `text` except unreserved characters.
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Decodes percent-encoded bytes and `+` signs in `text`, replacing invalid UTF-8 sequences.
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_repeated_keys_in_order() {
+        let query = "?tags=b&page=2&tags=a&tags=c";
+        assert_eq!(
+            read_query(query, "tags", QueryEncoding::Repeated),
+            Some(strings(&["b", "a", "c"]))
+        );
+    }
+
+    #[test]
+    fn reads_every_occurrence_with_commas() {
+        let query = "tags=a,b&tags=c";
+        assert_eq!(
+            read_query(query, "tags", QueryEncoding::Comma),
+            Some(strings(&["a", "b", "c"]))
+        );
+    }
+
+    #[test]
+    fn decodes_keys_and_values() {
+        let query = "?my%20tags=caf%C3%A9&my+tags=a%2Cb&my+tags=x+y";
+        assert_eq!(
+            read_query(query, "my tags", QueryEncoding::Repeated),
+            Some(strings(&["café", "a,b", "x y"]))
+        );
+        assert_eq!(
+            read_query("?t=a%2Cb,c", "t", QueryEncoding::Comma),
+            Some(strings(&["a,b", "c"]))
+        );
+    }
+
+    #[test]
+    fn keeps_invalid_escapes() {
+        assert_eq!(
+            read_query("?t=100%&t=%zz&t=%E2%82", "t", QueryEncoding::Repeated),
+            Some(strings(&["100%", "%zz", "\u{FFFD}"]))
+        );
+    }
+
+    #[test]
+    fn distinguishes_missing_and_empty_parameters() {
+        assert_eq!(read_query("", "t", QueryEncoding::Repeated), None);
+        assert_eq!(
+            read_query("?t", "t", QueryEncoding::Repeated),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            read_query("?t=&t=", "t", QueryEncoding::Repeated),
+            Some(Vec::new())
+        );
+        assert_eq!(read_query("?tags=a", "t", QueryEncoding::Repeated), None);
+    }
+
+    #[test]
+    fn writes_encoded_values() {
+        let values = strings(&["café", "a,b", "x&y=z"]);
+        assert_eq!(
+            write_query("", "my tags", &values, QueryEncoding::Repeated),
+            "?my%20tags=caf%C3%A9&my%20tags=a%2Cb&my%20tags=x%26y%3Dz"
+        );
+        assert_eq!(
+            write_query("", "t", &values, QueryEncoding::Comma),
+            "?t=caf%C3%A9,a%2Cb,x%26y%3Dz"
+        );
+    }
+
+    #[test]
+    fn replaces_repeated_keys_at_the_first_occurrence() {
+        let query = "?a=1&t=old&b=2&t=older";
+        let values = strings(&["x", "y"]);
+        assert_eq!(
+            write_query(query, "t", &values, QueryEncoding::Repeated),
+            "?a=1&t=x&t=y&b=2"
+        );
+        assert_eq!(
+            write_query(query, "t", &[], QueryEncoding::Repeated),
+            "?a=1&b=2"
+        );
+    }
+
+    #[test]
+    fn keeps_other_parameters_verbatim() {
+        let query = "?q=a+b&empty&x=%41";
+        assert_eq!(
+            write_query(query, "t", &strings(&["v"]), QueryEncoding::Comma),
+            "?q=a+b&empty&x=%41&t=v"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let values = strings(&["web apps", "a,b", "100%", "ü+"]);
+        for encoding in [QueryEncoding::Repeated, QueryEncoding::Comma] {
+            let query = write_query("?page=2", "tags", &values, encoding);
+            assert_eq!(read_query(&query, "tags", encoding), Some(values.clone()));
+            assert_eq!(read_query(&query, "page", encoding), Some(strings(&["2"])));
+        }
+    }
+}
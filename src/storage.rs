@@ -0,0 +1,213 @@
+//! Pluggable storage for state remembered across renders and sessions.
+//!
+//! Histories of recently chosen options are kept in a [`SelectStorage`], a minimal key-value interface, and
+//! selections in a [`SelectionStore`]. [`MemoryStorage`] keeps both in memory.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A key-value store for strings.
+///
+/// Implementations should not fail: a value that cannot be read is reported as missing, and a value that cannot
+/// be written is dropped.
+pub trait SelectStorage {
+    /// Returns the value stored under `key`, if any.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores `value` under `key`, replacing any previous value.
+    fn set(&self, key: &str, value: &str);
+
+    /// Returns the list of values stored under `key` with [`SelectStorage::set_list`], or an empty list.
+    fn get_list(&self, key: &str) -> Vec<String> {
+        self.get(key)
+            .map(|list| parse_list(&list))
+            .unwrap_or_default()
+    }
+
+    /// Stores a list of values under `key`, one per line.
+    fn set_list(&self, key: &str, values: &[String]) {
+        self.set(key, &values.join("\n"));
+    }
+}
+
+/// A store for the selection of a component, e.g. to restore it after a page reload.
+///
+/// Every [`SelectStorage`] is a selection store, keeping selections as lists. Implement this trait directly to
+/// store selections elsewhere, e.g. in the URL or on a server.
+///
+/// # Example
+/// ```rust
+/// use selectrs::storage::{MemoryStorage, SelectionStore};
+///
+/// let storage = MemoryStorage::default();
+/// assert_eq!(storage.load("filters"), None);
+/// storage.save("filters", &[]);
+/// assert_eq!(storage.load("filters"), Some(vec![]));
+/// storage.save("filters", &["open".to_string(), "mine".to_string()]);
+/// assert_eq!(storage.load("filters"), Some(vec!["open".to_string(), "mine".to_string()]));
+/// ```
+pub trait SelectionStore {
+    /// Returns the selection saved under `key`, or `None` if no selection was saved.
+    fn load(&self, key: &str) -> Option<Vec<String>>;
+
+    /// Saves the selection `values` under `key`.
+    fn save(&self, key: &str, values: &[String]);
+}
+
+impl<T: SelectStorage + ?Sized> SelectionStore for T {
+    fn load(&self, key: &str) -> Option<Vec<String>> {
+        self.get(key).map(|list| parse_list(&list))
+    }
+
+    fn save(&self, key: &str, values: &[String]) {
+        self.set_list(key, values);
+    }
+}
+
+/// Splits a list stored with [`SelectStorage::set_list`] into its values.
+fn parse_list(list: &str) -> Vec<String> {
+    list.lines()
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A storage keeping its values in memory, e.g. for tests or server-side rendering.
+///
+/// Clones share the same values, so a storage can be handed to several components.
+///
+/// # Example
+/// ```rust
+/// use selectrs::storage::{MemoryStorage, SelectStorage};
+///
+/// let storage = MemoryStorage::default();
+/// storage.clone().set_list("recent", &["rust".to_string(), "yew".to_string()]);
+/// assert_eq!(storage.get_list("recent"), ["rust", "yew"]);
+/// assert_eq!(storage.get("missing"), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStorage {
+    values: Rc<RefCell<HashMap<String, String>>>,
+}
+
+impl SelectStorage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+    }
+}
+
+/// Returns the history of recently chosen values once `chosen` are chosen, most recent first and limited to
+/// `size` values.
+///
+/// # Example
+/// ```rust
+/// use selectrs::storage::push_recent;
+///
+/// let history = vec!["b".to_string(), "c".to_string(), "d".to_string()];
+/// assert_eq!(push_recent(&history, &["a".to_string()], 3), ["a", "b", "c"]);
+/// assert_eq!(push_recent(&history, &["c".to_string()], 3), ["c", "b", "d"]);
+/// ```
+pub fn push_recent(history: &[String], chosen: &[String], size: usize) -> Vec<String> {
+    chosen
+        .iter()
+        .rev()
+        .chain(history.iter().filter(|value| !chosen.contains(value)))
+        .take(size)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn push_recent_puts_the_last_chosen_first() {
+        let history = strings(&["c", "d"]);
+        assert_eq!(
+            push_recent(&history, &strings(&["a", "b"]), 10),
+            ["b", "a", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn push_recent_moves_chosen_values_to_the_front_without_duplicates() {
+        let history = strings(&["a", "b", "c"]);
+        assert_eq!(
+            push_recent(&history, &strings(&["c", "a"]), 10),
+            ["a", "c", "b"]
+        );
+        assert_eq!(push_recent(&history, &strings(&["a"]), 10), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn push_recent_caps_the_history() {
+        let history = strings(&["a", "b", "c"]);
+        assert_eq!(
+            push_recent(&history, &strings(&["x", "y"]), 3),
+            ["y", "x", "a"]
+        );
+        assert_eq!(
+            push_recent(&history, &strings(&["w", "x", "y", "z"]), 2),
+            ["z", "y"]
+        );
+        assert!(push_recent(&history, &strings(&["x"]), 0).is_empty());
+    }
+
+    #[test]
+    fn push_recent_with_nothing_chosen_keeps_the_history() {
+        let history = strings(&["a", "b", "c"]);
+        assert_eq!(push_recent(&history, &[], 2), ["a", "b"]);
+    }
+
+    #[test]
+    fn memory_storage_round_trip() {
+        let storage = MemoryStorage::default();
+        assert_eq!(storage.get("key"), None);
+        storage.set("key", "value");
+        assert_eq!(storage.get("key").as_deref(), Some("value"));
+        storage.set("key", "other");
+        assert_eq!(storage.get("key").as_deref(), Some("other"));
+
+        let values = strings(&["rust", "web apps", "yew"]);
+        storage.set_list("list", &values);
+        assert_eq!(storage.get_list("list"), values);
+        assert!(storage.get_list("missing").is_empty());
+    }
+
+    #[test]
+    fn memory_storage_clones_share_values() {
+        let storage = MemoryStorage::default();
+        let clone = storage.clone();
+        clone.set("shared", "yes");
+        assert_eq!(storage.get("shared").as_deref(), Some("yes"));
+    }
+
+    #[test]
+    fn selection_store_distinguishes_empty_from_missing() {
+        let storage = MemoryStorage::default();
+        assert_eq!(storage.load("filters"), None);
+        storage.save("filters", &[]);
+        assert_eq!(storage.load("filters"), Some(Vec::new()));
+        storage.save("filters", &strings(&["open", "mine"]));
+        assert_eq!(storage.load("filters"), Some(strings(&["open", "mine"])));
+    }
+
+    #[test]
+    fn empty_lines_are_skipped() {
+        let storage = MemoryStorage::default();
+        storage.set("list", "a\n\nb\n");
+        assert_eq!(storage.get_list("list"), ["a", "b"]);
+    }
+}
@@ -0,0 +1,44 @@
+//! Optional built-in theme for the select components.
+//!
+//! The components are headless by default: they only render stable `selectrs-*` class names next to the
+//! classes passed through their props. This module ships a default stylesheet keyed on those class names.
+//! All of its colors, radii and spacings are read from CSS custom properties (`--selectrs-bg`,
+//! `--selectrs-accent`, `--selectrs-radius`, `--selectrs-spacing`, `--selectrs-chip-bg`, ...), which are
+//! defined for every element carrying a `data-selectrs-theme` attribute and can be overridden with plain CSS.
+
+/// The default stylesheet.
+///
+/// It is injected automatically by the framework theme providers, but can also be served or bundled
+/// manually, in which case the themed elements only need a `data-selectrs-theme` attribute.
+pub const STYLESHEET: &str = include_str!("theme.css");
+
+/// The color scheme of the default theme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectTheme {
+    /// Dark text on light backgrounds.
+    #[default]
+    Light,
+    /// Light text on dark backgrounds.
+    Dark,
+    /// Follows the `prefers-color-scheme` preference of the user.
+    Auto,
+}
+
+impl SelectTheme {
+    /// Returns the value of the `data-selectrs-theme` attribute selecting this variant, e.g. `"dark"`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use selectrs::theme::{SelectTheme, STYLESHEET};
+    ///
+    /// let selector = format!("[data-selectrs-theme=\"{}\"]", SelectTheme::Dark.as_str());
+    /// assert!(STYLESHEET.contains(&selector));
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SelectTheme::Light => "light",
+            SelectTheme::Dark => "dark",
+            SelectTheme::Auto => "auto",
+        }
+    }
+}
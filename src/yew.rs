@@ -1,6 +1,31 @@
+use crate::fuzzy::{fuzzy_match_with, FuzzyMatch};
+use crate::popup::{compute_position, PopupPosition, Rect};
+#[cfg(feature = "router")]
+use crate::query::{read_query, write_query};
+use crate::storage::push_recent;
+use gloo_events::{EventListener, EventListenerOptions};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Element, HtmlCollection, HtmlElement, HtmlFormElement, HtmlInputElement, HtmlOptionElement,
+    HtmlSelectElement, Node, ScrollIntoViewOptions, ScrollLogicalPosition,
+};
 use yew::prelude::*;
 
+#[cfg(feature = "serde")]
+pub use crate::config::{GroupConfig, OptionConfig, SelectConfig, SelectionState};
+pub use crate::locale::SelectLocale;
+pub use crate::normalize::Normalization;
+pub use crate::popup::{Direction, Placement};
+#[cfg(feature = "router")]
+pub use crate::query::QueryEncoding;
+pub use crate::storage::{MemoryStorage, SelectStorage, SelectionStore};
+#[cfg(feature = "theme")]
+pub use crate::theme::SelectTheme;
+
 /// Properties for configuring the `Select` component.
 ///
 /// The `Select` component creates a customizable dropdown list that allows you to choose
@@ -19,7 +44,7 @@ pub struct SelectProps {
     /// It is important when the component is part of a form, as it defines the field name.
     /// Defaults to an empty string if not provided.
     #[prop_or_default]
-    pub name: &'static str,
+    pub name: AttrValue,
 
     /// The id of the select component.
     ///
@@ -27,7 +52,7 @@ pub struct SelectProps {
     /// It helps in uniquely identifying the component within the DOM.
     /// Defaults to an empty string if not provided.
     #[prop_or_default]
-    pub id: &'static str,
+    pub id: AttrValue,
 
     /// The placeholder text for the select component.
     ///
@@ -35,7 +60,7 @@ pub struct SelectProps {
     /// It provides a hint to the user on what to select. It is not visible after an option is chosen.
     /// Defaults to an empty string if not provided.
     #[prop_or_default]
-    pub placeholder: &'static str,
+    pub placeholder: AttrValue,
 
     /// Whether the select component allows multiple selections.
     ///
@@ -44,6 +69,115 @@ pub struct SelectProps {
     #[prop_or_default]
     pub multiple: bool,
 
+    /// The values selected when the component mounts and after a form reset.
+    ///
+    /// Values without a matching option are ignored, and a selection restored through `persist` or `query_sync`
+    /// takes precedence on mount. Defaults to an empty list if not provided.
+    #[prop_or_default]
+    pub default_values: Vec<String>,
+
+    /// The minimum number of values that must be selected, if any.
+    ///
+    /// A smaller selection is reported as invalid with the `too_few` message of the locale, before the
+    /// `validate` callback runs. Defaults to `None` if not provided.
+    #[prop_or_default]
+    pub min_selected: std::option::Option<usize>,
+
+    /// The maximum number of values that can be selected, if any.
+    ///
+    /// Once it is reached, no further option can be added with the pointer, the keyboard or a `SelectHandle`,
+    /// and a larger selection, e.g. a default or restored one, is reported as invalid with the `too_many`
+    /// message of the locale. Defaults to `None` if not provided.
+    #[prop_or_default]
+    pub max_selected: std::option::Option<usize>,
+
+    /// Whether to render a custom popup list instead of the native `select` element.
+    ///
+    /// If set to `true`, the component renders a focusable trigger and a `listbox` popup built from
+    /// regular elements, which enables features a native `select` cannot provide, such as collapsible
+    /// groups. The popup supports keyboard navigation with the arrow, `Home`, `End`, `Enter` and `Escape` keys,
+    /// which also reaches the headers of collapsible groups.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub custom: bool,
+
+    /// Whether a search input filters the options of the custom popup list.
+    ///
+    /// While the popup is open, the trigger holds a search input. The options are fuzzy matched against the
+    /// query, listed by relevance rather than in groups, and the matched characters of their labels are
+    /// displayed in bold. Labels, descriptions and keywords are matched. Requires `custom`.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub searchable: bool,
+
+    /// How labels, descriptions, keywords and the query are normalized before searching.
+    ///
+    /// The default folds case according to the locale, decomposes characters and strips accents, so that
+    /// "creme" finds "Crème brûlée" and "muller" finds "Müller". Enable `transliterate` to also match letters
+    /// such as `ø` or Cyrillic letters by their ASCII spelling, or use `Normalization::NONE` for exact matches.
+    /// Defaults to `Normalization::default()` if not provided.
+    #[prop_or_default]
+    pub normalization: Normalization,
+
+    /// The history of recently chosen values, listed in a "Recent" section at the top of the list.
+    ///
+    /// The history keeps the last `size` chosen values, most recent first, and is updated on every change.
+    /// It is persisted under `key` in the configured storage, `localStorage` by default, so it survives page
+    /// reloads. Defaults to a `size` of `0`, disabling the history, if not provided.
+    #[prop_or_default]
+    pub recent: RecentConfig,
+
+    /// The values listed in a "Pinned" section at the top of the list, before recent values.
+    ///
+    /// Pinned options also remain in their groups, and values without a matching option are ignored. The section is
+    /// only rendered in custom mode, as is the "Recent" one.
+    /// Defaults to an empty list if not provided.
+    #[prop_or_default]
+    pub pinned: Vec<String>,
+
+    /// Where the selection is persisted, so that it survives page reloads.
+    ///
+    /// The selection is stored under a key derived from `name`, or from `id` if `name` is empty, and nothing is
+    /// persisted if both are empty. It is restored on mount, dropping values without a matching option and
+    /// reporting it through `onchange` if it differs from `default_values`, and saved on every change.
+    /// Defaults to `Persist::None` if not provided.
+    #[prop_or_default]
+    pub persist: Persist,
+
+    /// Binds the selection to a query parameter of the URL, e.g. `?tags=a,b`, so that links reproduce it.
+    ///
+    /// Every change navigates to the current URL with the parameter updated, pushing or replacing the history
+    /// entry, and navigating to another URL, e.g. with the back and forward buttons, selects its values. It needs
+    /// a `yew-router` router around the select and is only available with the `router` feature.
+    /// Defaults to no synchronization if not provided.
+    #[cfg(feature = "router")]
+    #[prop_or_default]
+    pub query_sync: QuerySync,
+
+    /// Whether to render the options as a tree of nested groups.
+    ///
+    /// If set to `true`, `Group` components can be nested to any depth and are rendered as expandable tree nodes.
+    /// Selection cascades: clicking a group header selects all of its descendant options, or deselects them if they
+    /// are all selected already. Tree mode always uses the custom popup list and allows multiple selections.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub tree: bool,
+
+    /// Which values a tree select reports through `onchange`.
+    ///
+    /// `TreeOutput::Leaves` reports every selected option value, while `TreeOutput::Ancestors` reports the
+    /// value of the highest fully selected group in place of its options. Only used when `tree` is `true`.
+    /// Defaults to `TreeOutput::Leaves` if not provided.
+    #[prop_or_default]
+    pub tree_output: TreeOutput,
+
+    /// The indentation applied per nesting level in tree mode.
+    ///
+    /// This is any CSS length and is applied as `padding-inline-start` to nested group headers and options.
+    /// Defaults to `"1rem"` if not provided.
+    #[prop_or("1rem")]
+    pub indent: &'static str,
+
     /// Whether the select component is disabled.
     ///
     /// If set to `true`, the select component will be unresponsive and users will not be able to interact with it.
@@ -98,20 +232,93 @@ pub struct SelectProps {
     #[prop_or_default]
     pub onchange: Callback<Vec<String>>,
 
+    /// Callback triggered with every value added to the selection.
+    ///
+    /// It is called once per value, after `onchange`, so selecting a whole group in tree mode calls it for every
+    /// reported value. Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub onselect: Callback<String>,
+
+    /// Callback triggered with every value removed from the selection.
+    ///
+    /// It is called once per value, after `onchange`. Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub ondeselect: Callback<String>,
+
+    /// Callback triggered with the values added and removed by a change and what caused it.
+    ///
+    /// It is called after `onchange` whenever values are added or removed, and when chips are reordered, with
+    /// empty lists and the `Reorder` source. Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub onchange_detail: Callback<SelectionChange>,
+
+    /// Custom validator for the selected values.
+    ///
+    /// This callback receives the values reported through `onchange` and returns `Err` with a message when
+    /// the selection is invalid, e.g. to require at least two tags or forbid combining two options. It runs
+    /// whenever the selection changes and when the focus leaves the component. The message is passed to
+    /// `setCustomValidity` on the underlying `select`, so native form submission is blocked while the selection
+    /// is invalid. Defaults to a validator that accepts every selection.
+    #[prop_or(Callback::from(|_| Ok(())))]
+    pub validate: Callback<Vec<String>, Result<(), String>>,
+
+    /// Callback triggered with the result of the `validate` callback.
+    ///
+    /// This callback is executed whenever the selection is validated, on change and on blur. Defaults to a
+    /// no-op if not provided.
+    #[prop_or_default]
+    pub onvalidate: Callback<Result<(), String>>,
+
+    /// Callback triggered when the focus enters the component.
+    ///
+    /// The component is treated as a whole: moving the focus between its `select`, trigger, search input and
+    /// chip buttons does not trigger it again. Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub onfocus: Callback<FocusEvent>,
+
+    /// Callback triggered when the focus leaves the component.
+    ///
+    /// Like `onfocus`, it only fires once the focus moves to an element outside of the component, after the
+    /// selection is validated. Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+
+    /// Callback triggered when the custom popup list opens.
+    ///
+    /// It is not triggered on mount, and never for native selects, whose dropdown cannot be observed.
+    /// Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub onopen: Callback<()>,
+
+    /// Callback triggered when the custom popup list closes, whatever the reason.
+    ///
+    /// Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub onclose: Callback<()>,
+
+    /// A handle controlling the component from the outside, e.g. from a keyboard shortcut or a toolbar.
+    ///
+    /// Create it with `use_select_handle()` and call its methods, such as `open()` or `clear()`, from callbacks.
+    /// Changes made through the handle go through the same path as user changes, so `onchange` and the other
+    /// callbacks report them with the `Programmatic` source. Defaults to a handle nobody holds if not provided.
+    #[prop_or_default]
+    pub handle: SelectHandle,
+
     /// Child components for the select component.
     ///
-    /// This property allows you to pass one or more `Group` components as children of the `Select` component.
-    /// The `Group` components contain the `Option` components, which represent the individual selectable options.
+    /// This property allows you to pass the `Group` and `Option` components of the select, which represent the
+    /// individual selectable options. They find the select through the `SelectContext` it provides, so they may
+    /// be wrapped in other components, mixed with other markup or rendered conditionally.
     /// Defaults to an empty list of children if not provided.
     #[prop_or_default]
-    pub children: ChildrenWithProps<Group>,
+    pub children: Children,
 
     /// Custom CSS class for the select container.
     ///
     /// This property allows for custom styling of the select container by specifying one or more CSS classes.
-    /// It is applied to the outer wrapper of the `select` element. Defaults to an empty string if not provided.
+    /// It is applied to the outer wrapper of the `select` element. Defaults to no classes if not provided.
     #[prop_or_default]
-    pub class: &'static str,
+    pub class: Classes,
 
     /// Inline styles for the select container.
     ///
@@ -124,9 +331,9 @@ pub struct SelectProps {
     /// Custom CSS class for the label container.
     ///
     /// This property allows for custom styling of the labels in the `Select` component. It applies to the wrapper
-    /// around the labels (for multi-selects or grouped selections). Defaults to an empty string if not provided.
+    /// around the labels (for multi-selects or grouped selections). Defaults to no classes if not provided.
     #[prop_or_default]
-    pub labels_class: &'static str,
+    pub labels_class: Classes,
 
     /// Inline styles for the label container.
     ///
@@ -139,9 +346,9 @@ pub struct SelectProps {
     /// Custom CSS class for the individual labels.
     ///
     /// This property allows for custom styling of the labels within the dropdown options.
-    /// Defaults to an empty string if not provided.
+    /// Defaults to no classes if not provided.
     #[prop_or_default]
-    pub label_class: &'static str,
+    pub label_class: Classes,
 
     /// Inline styles for the individual labels.
     ///
@@ -154,9 +361,9 @@ pub struct SelectProps {
     /// Custom CSS class for the close button (for multi-select).
     ///
     /// This property allows for custom styling of the close button that appears next to selected values in a multi-select dropdown.
-    /// Defaults to an empty string if not provided.
+    /// Defaults to no classes if not provided.
     #[prop_or_default]
-    pub close_class: &'static str,
+    pub close_class: Classes,
 
     /// Inline styles for the close button (for multi-select).
     ///
@@ -169,9 +376,9 @@ pub struct SelectProps {
     /// Custom CSS class for the select dropdown.
     ///
     /// This property allows for custom styling of the select dropdown box itself. This class is applied to the
-    /// `select` element in the rendered HTML. Defaults to an empty string if not provided.
+    /// `select` element in the rendered HTML. Defaults to no classes if not provided.
     #[prop_or_default]
-    pub select_class: &'static str,
+    pub select_class: Classes,
 
     /// Inline styles for the select dropdown.
     ///
@@ -180,6 +387,196 @@ pub struct SelectProps {
     /// Defaults to an empty string if not provided.
     #[prop_or_default]
     pub select_style: &'static str,
+
+    /// Custom CSS class for the popup list (custom mode only).
+    ///
+    /// This property allows for custom styling of the `listbox` popup that is rendered below the trigger
+    /// when `custom` is set to `true`. Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub popup_class: Classes,
+
+    /// Inline styles for the popup list (custom mode only).
+    ///
+    /// This property allows for custom inline styles to be applied directly to the `listbox` popup,
+    /// such as its maximum height or overflow behavior. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub popup_style: &'static str,
+
+    /// Whether to position the popup next to the trigger with `position: fixed` (custom mode only).
+    ///
+    /// If set to `true`, the popup is placed according to `placement`, flipped to the other side or edge of the
+    /// trigger when it would overflow the viewport, shifted back into the viewport otherwise, and repositioned
+    /// whenever the page is scrolled or resized. Its maximum height is limited to the available space.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub floating: bool,
+
+    /// Whether to render the popup into a portal (custom mode only).
+    ///
+    /// This moves the popup out of the select container, so it is not clipped by ancestors with
+    /// `overflow: hidden`, such as table cells or modals. The popup is rendered into `portal_host`, or into the
+    /// document `body` if no host is given. Implies `floating`. Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub portal: bool,
+
+    /// The element the popup is rendered into when `portal` is enabled.
+    ///
+    /// Defaults to the document `body` if not provided.
+    #[prop_or_default]
+    pub portal_host: std::option::Option<Element>,
+
+    /// The preferred placement of a floating popup relative to the trigger.
+    ///
+    /// Defaults to `Placement::BottomStart` if not provided.
+    #[prop_or_default]
+    pub placement: Placement,
+
+    /// The distance between the trigger and a floating popup, in pixels.
+    ///
+    /// Defaults to `0.0` if not provided.
+    #[prop_or_default]
+    pub popup_offset: f64,
+
+    /// Whether a floating popup has the same width as the trigger.
+    ///
+    /// Defaults to `true` if not provided.
+    #[prop_or(true)]
+    pub match_width: bool,
+
+    /// Custom CSS class for the validation error message.
+    ///
+    /// This property allows for custom styling of the element displaying the error returned by `validate`.
+    /// The element is only rendered once the select has been changed or blurred and the selection is invalid.
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub error_class: Classes,
+
+    /// Inline styles for the validation error message.
+    ///
+    /// This property allows for custom inline styles to be applied directly to the validation error message.
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub error_style: &'static str,
+
+    /// Renders each chip of a multiple select in place of the default chip markup.
+    ///
+    /// The callback receives a `ChipContext` describing the chip, including a `remove` callback to deselect its
+    /// value. The returned markup is wrapped in the chip element carrying `label_class` and `label_style`.
+    /// Defaults to `None`, rendering the chip label followed by a close button.
+    #[prop_or_default]
+    pub render_chip: std::option::Option<Callback<ChipContext, Html>>,
+
+    /// The content of the close button of the default chips.
+    ///
+    /// Defaults to the text `"x"` if not provided.
+    #[prop_or_else(default_close_icon)]
+    pub close_icon: Html,
+
+    /// Renders the placeholder in place of the `placeholder` text.
+    ///
+    /// The callback receives a `SlotContext` describing the select. In native mode the placeholder is rendered
+    /// inside an `option` element, where browsers only display text. Defaults to `None`, rendering `placeholder`.
+    #[prop_or_default]
+    pub render_placeholder: std::option::Option<Callback<SlotContext, Html>>,
+
+    /// Renders an indicator at the end of the trigger, such as a chevron (custom mode only).
+    ///
+    /// The callback receives a `SlotContext`, whose `open` field can be used to rotate the indicator.
+    /// Defaults to `None`, rendering no indicator.
+    #[prop_or_default]
+    pub dropdown_indicator: std::option::Option<Callback<SlotContext, Html>>,
+
+    /// Renders content before the trigger, such as an icon.
+    ///
+    /// The callback receives a `SlotContext` describing the select. Defaults to `None`, rendering nothing.
+    #[prop_or_default]
+    pub prefix: std::option::Option<Callback<SlotContext, Html>>,
+
+    /// Renders content after the trigger, such as a hint or a clear button.
+    ///
+    /// The callback receives a `SlotContext` describing the select. Defaults to `None`, rendering nothing.
+    #[prop_or_default]
+    pub suffix: std::option::Option<Callback<SlotContext, Html>>,
+
+    /// Renders the content of the popup when it has no option to choose from (custom mode only).
+    ///
+    /// The callback receives a `SlotContext` describing the select. Defaults to `None`, rendering the
+    /// `no_results` message of the locale.
+    #[prop_or_default]
+    pub empty_state: std::option::Option<Callback<SlotContext, Html>>,
+
+    /// The built-in strings and the language of this instance.
+    ///
+    /// This overrides the locale provided by an enclosing `SelectLocaleProvider`. Defaults to the provided
+    /// locale, or to `SelectLocale::default()` with English strings if none is provided.
+    #[prop_or_default]
+    pub locale: std::option::Option<SelectLocale>,
+
+    /// The writing direction of the component.
+    ///
+    /// In `Direction::Rtl` the layout is mirrored, the left and right arrow keys follow the visual direction
+    /// and a floating popup aligns with the right edge of the trigger. Defaults to the direction provided by an
+    /// enclosing `ContextProvider<Direction>`, or to `Direction::Ltr` if none is provided.
+    #[prop_or_default]
+    pub dir: std::option::Option<Direction>,
+
+    /// How the options of each group are sorted.
+    ///
+    /// `SortOptions::Alphabetical` sorts them by label according to the language of the locale, and
+    /// `SortOptions::Comparator` with a custom comparator. Nested groups keep their position among the
+    /// options. Defaults to `SortOptions::None`, keeping the order of the children, if not provided.
+    #[prop_or_default]
+    pub sort_options: SortOptions,
+
+    /// Whether the selected options are listed first within each group.
+    ///
+    /// The selected options keep the order given by `sort_options` among themselves.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub selected_first: bool,
+
+    /// The order of the selected values, as displayed as chips and reported through `onchange`.
+    ///
+    /// `ChipOrder::Selection` keeps the order in which the values were selected, while `ChipOrder::Options`
+    /// follows the order of the options as sorted by `sort_options`. Defaults to `ChipOrder::Selection` if
+    /// not provided.
+    #[prop_or_default]
+    pub chip_order: ChipOrder,
+
+    /// Whether the chips can be reordered in `multiple` mode.
+    ///
+    /// Chips are moved by dragging them with the pointer, or with `Alt` and the left and right arrow keys
+    /// while a chip is focused. Reordering is not available with `ChipOrder::Options`, or when chips stand
+    /// for whole groups in tree mode. Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub reorderable: bool,
+
+    /// Callback triggered when a chip is moved to another position.
+    ///
+    /// It receives the moved value and its previous and new positions, while `onchange` is emitted with
+    /// all values in their new order. Defaults to a no-op callback if not provided.
+    #[prop_or_default]
+    pub onreorder: Callback<ChipReorder>,
+
+    /// The maximum number of chips displayed in `multiple` mode.
+    ///
+    /// Further chips are collapsed into a "+N more" chip, which expands them when clicked and describes the full
+    /// selection in a tooltip. Defaults to `None`, displaying every chip, if not provided.
+    #[prop_or_default]
+    pub max_visible_chips: std::option::Option<usize>,
+
+    /// How the selected values are displayed in `multiple` mode.
+    ///
+    /// `DisplayMode::Count` replaces the chips with a summary such as "3 selected", and `DisplayMode::CommaList`
+    /// with the comma-separated labels. Both summaries describe the full selection in a tooltip shown on hover
+    /// and focus. Defaults to `DisplayMode::Chips` if not provided.
+    #[prop_or_default]
+    pub display_mode: DisplayMode,
+}
+
+/// Returns the default content of the chip close button.
+fn default_close_icon() -> Html {
+    html! { "x" }
 }
 
 /// Select Component
@@ -190,23 +587,72 @@ pub struct SelectProps {
 /// # Properties
 /// The component uses the `SelectProps` struct for its properties. Key properties include:
 ///
-/// - **name**: The name of the select element (`&'static str`). Default: `""`.
-/// - **id**: The ID of the select element (`&'static str`). Default: `""`.
-/// - **placeholder**: Placeholder text for the select input when no options are selected (`&'static str`). Default: `""`.
+/// - **name**: The name of the select element (`AttrValue`). Default: `""`.
+/// - **id**: The ID of the select element (`AttrValue`). Default: `""`.
+/// - **placeholder**: Placeholder text for the select input when no options are selected (`AttrValue`). Default: `""`.
 /// - **multiple**: Whether the select allows multiple selections (`bool`). Default: `false`.
+/// - **default_values**: The values selected on mount and after a form reset (`Vec<String>`). Default: empty.
+/// - **min_selected**: The minimum number of selected values (`Option<usize>`). Default: `None`.
+/// - **max_selected**: The maximum number of selected values (`Option<usize>`). Default: `None`.
+/// - **custom**: Whether to render a custom popup list instead of the native `select` (`bool`). Default: `false`.
+/// - **searchable**: Whether a search input fuzzy filters the options of the custom popup (`bool`). Default: `false`.
+/// - **normalization**: How text is normalized before searching (`Normalization`). Default: case, accent and compatibility insensitive.
+/// - **recent**: The size, storage key and storage of the recently chosen values (`RecentConfig`). Default: disabled.
+/// - **pinned**: The values listed in a "Pinned" section at the top of the list (`Vec<String>`). Default: empty.
+/// - **persist**: Where the selection is persisted across page reloads (`Persist`). Default: `Persist::None`.
+/// - **query_sync**: The query parameter bound to the selection, with the `router` feature (`QuerySync`). Default: disabled.
+/// - **tree**: Whether to render nested groups as a tree with cascading selection (`bool`). Default: `false`.
+/// - **tree_output**: Which values a tree select reports through `onchange` (`TreeOutput`). Default: `TreeOutput::Leaves`.
+/// - **indent**: The CSS indentation applied per nesting level in tree mode (`&'static str`). Default: `"1rem"`.
 /// - **disabled**: Whether the select element is disabled (`bool`). Default: `false`.
 /// - **onchange**: Callback triggered when the selected values change (`Callback<Vec<String>>`). Default: no-op.
-/// - **children**: A collection of `Option` components as children (`ChildrenWithProps<Option>`). Default: empty.
-/// - **class**: Custom CSS class for the select container (`&'static str`). Default: `""`.
+/// - **onselect**: Callback triggered with every added value (`Callback<String>`). Default: no-op.
+/// - **ondeselect**: Callback triggered with every removed value (`Callback<String>`). Default: no-op.
+/// - **onchange_detail**: Callback receiving the added and removed values and the source of a change (`Callback<SelectionChange>`). Default: no-op.
+/// - **validate**: Custom validator returning an error message for invalid selections (`Callback<Vec<String>, Result<(), String>>`). Default: accepts everything.
+/// - **onvalidate**: Callback receiving the validation result on change and blur (`Callback<Result<(), String>>`). Default: no-op.
+/// - **onfocus**: Callback triggered when the focus enters the component (`Callback<FocusEvent>`). Default: no-op.
+/// - **onblur**: Callback triggered when the focus leaves the component (`Callback<FocusEvent>`). Default: no-op.
+/// - **onopen**: Callback triggered when the custom popup opens (`Callback<()>`). Default: no-op.
+/// - **onclose**: Callback triggered when the custom popup closes (`Callback<()>`). Default: no-op.
+/// - **handle**: A handle to open, focus or change the select programmatically (`SelectHandle`). Default: none.
+/// - **children**: The `Group` and `Option` components, at any depth of the component tree (`Children`). Default: empty.
+/// - **class**: Custom CSS class for the select container (`Classes`). Default: empty.
 /// - **style**: Inline styles for the select container (`&'static str`). Default: `""`.
-/// - **labels_class**: Custom class for the selected options' labels (`&'static str`). Default: `""`.
+/// - **labels_class**: Custom class for the selected options' labels (`Classes`). Default: empty.
 /// - **labels_style**: Inline styles for the selected options' labels (`&'static str`). Default: `""`.
-/// - **label_class**: Custom class for each label when an option is selected (`&'static str`). Default: `""`.
+/// - **label_class**: Custom class for each label when an option is selected (`Classes`). Default: empty.
 /// - **label_style**: Inline styles for each label when an option is selected (`&'static str`). Default: `""`.
-/// - **close_class**: Custom class for the close button (`&'static str`). Default: `""`.
+/// - **close_class**: Custom class for the close button (`Classes`). Default: empty.
 /// - **close_style**: Inline styles for the close button (`&'static str`). Default: `""`.
-/// - **select_class**: Custom CSS class for the select element itself (`&'static str`). Default: `""`.
+/// - **select_class**: Custom CSS class for the select element itself (`Classes`). Default: empty.
 /// - **select_style**: Inline styles for the select element (`&'static str`). Default: `""`.
+/// - **popup_class**: Custom CSS class for the popup list in custom mode (`Classes`). Default: empty.
+/// - **popup_style**: Inline styles for the popup list in custom mode (`&'static str`). Default: `""`.
+/// - **floating**: Whether to position the popup next to the trigger with collision detection (`bool`). Default: `false`.
+/// - **portal**: Whether to render the popup into a portal, implying `floating` (`bool`). Default: `false`.
+/// - **portal_host**: The element the popup is portaled into (`Option<Element>`). Default: the document `body`.
+/// - **placement**: The preferred placement of a floating popup (`Placement`). Default: `Placement::BottomStart`.
+/// - **popup_offset**: The distance between the trigger and a floating popup in pixels (`f64`). Default: `0.0`.
+/// - **match_width**: Whether a floating popup has the same width as the trigger (`bool`). Default: `true`.
+/// - **error_class**: Custom CSS class for the validation error message (`Classes`). Default: empty.
+/// - **error_style**: Inline styles for the validation error message (`&'static str`). Default: `""`.
+/// - **render_chip**: Renders each chip from a `ChipContext` (`Option<Callback<ChipContext, Html>>`). Default: `None`.
+/// - **close_icon**: The content of the default chip close button (`Html`). Default: `"x"`.
+/// - **render_placeholder**: Renders the placeholder from a `SlotContext` (`Option<Callback<SlotContext, Html>>`). Default: `None`.
+/// - **dropdown_indicator**: Renders an indicator at the end of the custom trigger (`Option<Callback<SlotContext, Html>>`). Default: `None`.
+/// - **prefix**: Renders content before the trigger (`Option<Callback<SlotContext, Html>>`). Default: `None`.
+/// - **suffix**: Renders content after the trigger (`Option<Callback<SlotContext, Html>>`). Default: `None`.
+/// - **empty_state**: Renders the content of a popup without options (`Option<Callback<SlotContext, Html>>`). Default: `None`.
+/// - **locale**: The built-in strings and language of this instance (`Option<SelectLocale>`). Default: the provided locale.
+/// - **dir**: The writing direction, mirroring the layout and arrow keys (`Option<Direction>`). Default: the provided direction.
+/// - **sort_options**: How the options of each group are sorted (`SortOptions`). Default: `SortOptions::None`.
+/// - **selected_first**: Whether selected options are listed first within each group (`bool`). Default: `false`.
+/// - **chip_order**: The order of the chips and reported values (`ChipOrder`). Default: `ChipOrder::Selection`.
+/// - **reorderable**: Whether the chips can be reordered by dragging or with `Alt` and the arrow keys (`bool`). Default: `false`.
+/// - **onreorder**: Callback triggered when a chip is moved (`Callback<ChipReorder>`). Default: no-op.
+/// - **max_visible_chips**: The maximum number of chips before a "+N more" chip (`Option<usize>`). Default: `None`.
+/// - **display_mode**: How the selected values are displayed in multiple mode (`DisplayMode`). Default: `DisplayMode::Chips`.
 /// - **size**: The number of visible options in a scrolling select (`usize`). Default: `0`.
 /// - **required**: Whether the select field is required (`bool`). Default: `false`.
 /// - **form**: The ID of the form that the select is associated with (`&'static str`). Default: `""`.
@@ -218,6 +664,27 @@ pub struct SelectProps {
 /// - Customizable via CSS classes and inline styles.
 /// - Optionally displays a placeholder and manages selected items with chips (for multiple selections).
 /// - Trigger an `onchange` callback whenever the selection changes.
+/// - Optionally renders a custom, keyboard-navigable popup list with collapsible groups.
+/// - Supports hierarchical tree selection with nested groups and cascading selection.
+/// - Validates the selection with custom validators integrated with native form validation.
+/// - Positions the popup with viewport collision detection, optionally rendered into a portal.
+/// - Renders stable `selectrs-*` class names, styled by the optional built-in theme of the `theme` feature.
+/// - Replaces the chip, placeholder, indicator, prefix, suffix and empty popup markup through render slots.
+/// - Localizes its built-in strings through a `SelectLocale`, provided by context or per instance.
+/// - Supports right-to-left layouts, with mirrored arrow keys and popup alignment.
+/// - Sorts the options alphabetically or with a custom comparator, optionally listing selected options first.
+/// - Reorders chips with the pointer or the keyboard, e.g. to build ordered priority lists.
+/// - Collapses extra chips into a "+N more" chip, or summarizes the selection as a count or comma-separated list.
+/// - Renders options with an icon, a description line and a badge in custom mode.
+/// - Fuzzy searches the options, listing the best matches first with the matched characters in bold.
+/// - Ignores case and accents when searching, with a configurable normalization pipeline.
+/// - Lists pinned and recently chosen options at the top, with the history persisted in a pluggable storage.
+/// - Persists the selection in `localStorage`, `sessionStorage` or a custom store, restoring it on mount.
+/// - Synchronizes the selection with a URL query parameter through `yew-router`, for shareable links.
+/// - Reports added and removed values and the cause of every change, without diffing selections.
+/// - Reports focus entering and leaving the component as a whole, and the popup opening and closing.
+/// - Opens, focuses, clears and changes the selection programmatically through a `SelectHandle`.
+/// - Accepts any component tree between the select and its options, such as wrappers, separators and conditionals.
 ///
 /// # Examples
 ///
@@ -315,348 +782,5372 @@ pub struct SelectProps {
 /// }
 /// ```
 ///
-/// # Behavior
-/// - The `Select` component handles single and multiple selections dynamically.
-/// - The selected values are updated using the `onchange` callback whenever the user interacts with the select options.
-/// - When multiple selection mode is enabled, selected values are displayed as chips with a close button to remove individual selections.
-/// - A placeholder option is displayed when no value is selected and the select is not disabled.
+/// ## Custom Validation
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group};
 ///
-/// # Notes
-/// - The `children` property must contain `Option` components to populate the select dropdown.
-/// - If the `multiple` property is `true`, multiple options can be selected at once. If `false`, only one option can be selected.
-/// - Custom styling can be applied to the select container, options, and labels via CSS classes or inline styles.
-#[function_component(Select)]
-pub fn select(props: &SelectProps) -> Html {
-    let SelectProps {
-        name,
-        id,
-        placeholder,
-        multiple,
-        disabled,
-        onchange,
-        children,
-        class,
-        style,
-        labels_class,
-        labels_style,
-        label_class,
-        label_style,
-        close_class,
-        close_style,
-        select_class,
-        select_style,
-        size,
-        required,
-        form,
-        autocomplete,
-        autofocus,
-    } = props.clone();
-
-    let selected_values = use_state(Vec::<String>::new);
-    let selected = (*selected_values).clone();
-
-    let handle_group_change = {
-        let selected_values = selected_values.clone();
-        let on_change = onchange.clone();
-        Callback::from(move |value: String| {
-            let mut current_values = (*selected_values).clone();
-            if multiple {
-                if current_values.contains(&value) {
-                    current_values.retain(|v| v != &value);
-                } else {
-                    current_values.push(value);
-                }
-            } else {
-                current_values = vec![value];
-            }
-            selected_values.set(current_values.clone());
-            on_change.emit(current_values);
-        })
-    };
-
-    let remove_chip = {
-        let selected_values = selected_values.clone();
-        let on_change = onchange.clone();
-        Callback::from(move |value: String| {
-            let mut current_values = (*selected_values).clone();
-            current_values.retain(|v| v != &value);
-            selected_values.set(current_values.clone());
-            on_change.emit(current_values);
-        })
-    };
-
-    html! {
-        <div class={class} style={style}>
-            { if multiple {
-                html! {
-                    <div class={labels_class} style={labels_style}>
-                        { for selected.clone().into_iter().map(|value| html! {
-                            <div class={label_class} style={label_style}>
-                                { value.clone() }
-                                <button class={close_class} style={close_style} onclick={remove_chip.clone().reform(move |_| value.clone())}>
-                                    { "x" }
-                                </button>
-                            </div>
-                        }) }
-                    </div>
-                }
-            } else {
-                html! {}
-            } }
-            <select
-                id={id}
-                name={name}
-                multiple={multiple}
-                class={select_class}
-                style={select_style}
-                disabled={disabled}
-                size={size.to_string()}
-                required={required}
-                form={form}
-                autocomplete={autocomplete}
-                autofocus={autofocus}
-            >
-                { if (!placeholder.is_empty() && selected.is_empty()) || disabled {
-                    html! { <option disabled=true selected=true>{ placeholder }</option> }
-                } else {
-                    html! {}
-                } }
-                if !disabled {
-                    { for children.iter().map(|mut child| {
-                    let props = Rc::make_mut(&mut child.props);
-
-                    props.selected = selected.first().cloned().unwrap_or_default();
-                    let handle_group_change = handle_group_change.clone();
-                    props.onchange = Callback::from(move |value| handle_group_change.emit(value));
-
-                    child
-                }) }
-                }
-            </select>
-        </div>
-    }
-}
-
-/// Properties for configuring the `Group` component.
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let validate = Callback::from(|values: Vec<String>| {
+///         if values.len() < 2 {
+///             Err("Pick at least 2 tags.".to_string())
+///         } else {
+///             Ok(())
+///         }
+///     });
 ///
-/// The `Group` component allows you to group together `Option` elements.
-/// It provides customization for labels, selection behavior, and event handling. The group allows a common state and
-/// behavior across the contained options. This component supports customization of styles and classes, as well as
-/// interaction handling through the `onchange` callback.
-#[derive(Properties, PartialEq, Clone)]
-pub struct GroupProps {
-    /// The label for the group.
-    ///
-    /// This is the text that labels the entire group of options. This can be used to describe
-    /// the set of options the user is about to choose from, making it useful for accessibility.
-    /// Defaults to an empty string if not provided.
-    #[prop_or_default]
-    pub label: &'static str,
-
-    /// Indicates whether this is a group options.
-    ///
+///     html! {
+///         <Select multiple=true name="tags" validate={validate} error_class="error">
+///             <Group>
+///                 <Option value="rust" label="Rust" />
+///                 <Option value="wasm" label="Wasm" />
+///                 <Option value="yew" label="Yew" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Custom Popup with Collapsible Groups
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <Select custom=true multiple=true placeholder="Pick some food...">
+///             <Group label="Fruits" group=true collapsible=true>
+///                 <Option value="apple" label="Apple" />
+///                 <Option value="banana" label="Banana" />
+///             </Group>
+///             <Group
+///                 label="Vegetables"
+///                 group=true
+///                 collapsible=true
+///                 default_collapsed=true
+///                 ontoggle={Callback::from(|collapsed: bool| {
+///                     log::info!("Vegetables collapsed: {}", collapsed);
+///                 })}
+///             >
+///                 <Option value="carrot" label="Carrot" />
+///                 <Option value="broccoli" label="Broccoli" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Render Slots
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{ChipContext, Select, Option, Group, SlotContext};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let render_chip = Callback::from(|chip: ChipContext| html! {
+///         <>
+///             <strong>{ chip.label }</strong>
+///             <button disabled={chip.disabled} onclick={chip.remove.reform(|_| ())}>{ "✕" }</button>
+///         </>
+///     });
+///     let dropdown_indicator = Callback::from(|context: SlotContext| html! {
+///         { if context.open { "▴" } else { "▾" } }
+///     });
+///
+///     html! {
+///         <Select
+///             custom=true
+///             multiple=true
+///             placeholder="Pick some fruits..."
+///             render_chip={render_chip}
+///             dropdown_indicator={dropdown_indicator}
+///             prefix={Callback::from(|_| html! { "🍎" })}
+///             empty_state={Callback::from(|_| html! { "Nothing to pick" })}
+///         >
+///             <Group>
+///                 <Option value="apple" label="Apple" />
+///                 <Option value="banana" label="Banana" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Right-to-Left Layout
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Direction, Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <ContextProvider<Direction> context={Direction::Rtl}>
+///             <Select custom=true multiple=true floating=true placeholder="اختر الفواكه...">
+///                 <Group>
+///                     <Option value="apple" label="تفاح" />
+///                     <Option value="banana" label="موز" />
+///                 </Group>
+///             </Select>
+///         </ContextProvider<Direction>>
+///     }
+/// }
+/// ```
+///
+/// ## Sorted Options
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{ChipOrder, Select, SelectLocale, SortOptions, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let locale = SelectLocale { language: "sv", ..SelectLocale::default() };
+///     html! {
+///         <Select
+///             multiple=true
+///             custom=true
+///             locale={locale}
+///             sort_options={SortOptions::Alphabetical}
+///             selected_first=true
+///             chip_order={ChipOrder::Options}
+///         >
+///             <Group>
+///                 <Option value="orebro" label="Örebro" />
+///                 <Option value="uppsala" label="Uppsala" />
+///                 <Option value="alvsjo" label="Älvsjö" />
+///                 <Option value="abisko" label="Abisko" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Reorderable Chips
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{ChipReorder, Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let last_move = use_state(|| None::<ChipReorder>);
+///     let onreorder = {
+///         let last_move = last_move.clone();
+///         Callback::from(move |change: ChipReorder| last_move.set(Some(change)))
+///     };
+///     html! {
+///         <>
+///             <Select multiple=true reorderable=true onreorder={onreorder} placeholder="Rank your priorities...">
+///                 <Group>
+///                     <Option value="speed" label="Speed" />
+///                     <Option value="cost" label="Cost" />
+///                     <Option value="quality" label="Quality" />
+///                 </Group>
+///             </Select>
+///             if let Some(change) = &*last_move {
+///                 <p>{ format!("Moved {} from {} to {}", change.value, change.from + 1, change.to + 1) }</p>
+///             }
+///         </>
+///     }
+/// }
+/// ```
+///
+/// ## Collapsed Chips and Summaries
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{DisplayMode, Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <>
+///             <Select multiple=true max_visible_chips={Some(2)} placeholder="Select tags...">
+///                 <Group>
+///                     <Option value="rust" label="Rust" />
+///                     <Option value="wasm" label="WebAssembly" />
+///                     <Option value="yew" label="Yew" />
+///                     <Option value="web" label="Web" />
+///                 </Group>
+///             </Select>
+///             <Select multiple=true custom=true display_mode={DisplayMode::Count} placeholder="Select tags...">
+///                 <Group>
+///                     <Option value="rust" label="Rust" />
+///                     <Option value="wasm" label="WebAssembly" />
+///                 </Group>
+///             </Select>
+///         </>
+///     }
+/// }
+/// ```
+///
+/// ## Options with Icons, Descriptions and Badges
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <Select custom=true placeholder="Select a plan...">
+///             <Group>
+///                 <Option
+///                     value="free"
+///                     label="Free"
+///                     icon={html! { "🌱" }}
+///                     description="For personal projects"
+///                 />
+///                 <Option
+///                     value="pro"
+///                     label="Pro"
+///                     icon={html! { "🚀" }}
+///                     description="For growing teams"
+///                     badge="Popular"
+///                     keywords={vec!["team".to_string(), "business".to_string()]}
+///                 />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Fuzzy Search
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <Select custom=true searchable=true placeholder="Select a city...">
+///             <Group label="United States">
+///                 <Option value="nyc" label="New York City" keywords={vec!["Big Apple".to_string()]} />
+///                 <Option value="sf" label="San Francisco" />
+///             </Group>
+///             <Group label="Canada">
+///                 <Option value="yyz" label="Toronto" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Pinned and Recent Options
+/// ```rust
+/// use std::rc::Rc;
+/// use yew::prelude::*;
+/// use selectrs::yew::{MemoryStorage, RecentConfig, Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     // Keeps the history in memory rather than in `localStorage`.
+///     let recent = RecentConfig {
+///         storage: Rc::new(MemoryStorage::default()),
+///         ..RecentConfig::new("assignees", 3)
+///     };
+///     html! {
+///         <Select custom=true recent={recent} pinned={vec!["me".to_string()]} placeholder="Assign to...">
+///             <Group>
+///                 <Option value="me" label="Me" />
+///                 <Option value="alice" label="Alice" />
+///                 <Option value="bob" label="Bob" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Persisted Selection
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Persist, Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     // Restores the filters chosen earlier in this tab after a reload.
+///     html! {
+///         <Select name="status" multiple=true persist={Persist::Session} placeholder="Filter by status...">
+///             <Group>
+///                 <Option value="open" label="Open" />
+///                 <Option value="closed" label="Closed" />
+///                 <Option value="draft" label="Draft" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Selection Events
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{ChangeSource, Select, SelectionChange, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let last_removed = use_state(String::new);
+///     let ondeselect = {
+///         let last_removed = last_removed.clone();
+///         Callback::from(move |value: String| last_removed.set(value))
+///     };
+///     let onchange_detail = Callback::from(|change: SelectionChange| {
+///         if change.source == ChangeSource::ChipRemove {
+///             // E.g. offer to undo the removal of `change.removed`.
+///         }
+///     });
+///     html! {
+///         <Select multiple=true ondeselect={ondeselect} onchange_detail={onchange_detail}>
+///             <Group>
+///                 <Option value="apple" label="Apple" />
+///                 <Option value="banana" label="Banana" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Focus and Popup Events
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let opened = use_state(|| false);
+///     let onopen = {
+///         let opened = opened.clone();
+///         // E.g. start loading the options the first time the popup opens.
+///         Callback::from(move |_| opened.set(true))
+///     };
+///     let onblur = Callback::from(|_: FocusEvent| {
+///         // The focus left the whole component, not only the trigger.
+///     });
+///     html! {
+///         <Select custom=true multiple=true onopen={onopen} onblur={onblur}>
+///             <Group>
+///                 <Option value="apple" label="Apple" />
+///                 <Option value="banana" label="Banana" />
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Floating Popup in a Portal
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group, Placement};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <div style="overflow: hidden; height: 3rem;">
+///             <Select custom=true portal=true placement={Placement::BottomEnd} popup_offset=4.0>
+///                 <Group>
+///                     <Option value="small" label="Small" />
+///                     <Option value="medium" label="Medium" />
+///                     <Option value="large" label="Large" />
+///                 </Group>
+///             </Select>
+///         </div>
+///     }
+/// }
+/// ```
+///
+/// ## Tree Select with Nested Groups
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group, TreeOutput};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <Select
+///             tree=true
+///             tree_output={TreeOutput::Ancestors}
+///             placeholder="Pick categories..."
+///             onchange={Callback::from(|selected_values: Vec<String>| {
+///                 log::info!("Selected: {:?}", selected_values);
+///             })}
+///         >
+///             <Group label="Electronics" value="electronics" group=true>
+///                 <Group label="Computers" value="computers" group=true>
+///                     <Option value="laptops" label="Laptops" />
+///                     <Option value="desktops" label="Desktops" />
+///                 </Group>
+///                 <Group label="Phones" value="phones" group=true default_collapsed=true>
+///                     <Option value="android" label="Android" />
+///                     <Option value="ios" label="iOS" />
+///                 </Group>
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// ## Custom Option Components
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{use_select_context, Select, Option, Group};
+///
+/// #[derive(Properties, PartialEq)]
+/// pub struct FruitProps {
+///     pub value: &'static str,
+///     pub label: &'static str,
+/// }
+///
+/// // Options may be wrapped in components, which can read the selection from the context.
+/// #[function_component(Fruit)]
+/// pub fn fruit(props: &FruitProps) -> Html {
+///     let selected = use_select_context().is_some_and(|context| context.is_selected(props.value));
+///
+///     html! {
+///         <Option value={props.value} label={props.label} badge={if selected { "✓" } else { "" }} />
+///     }
+/// }
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let show_exotic = use_state(|| false);
+///
+///     html! {
+///         <Select custom=true multiple=true placeholder="Pick fruits...">
+///             <Group label="Fruits" group=true>
+///                 <Fruit value="apple" label="Apple" />
+///                 <Fruit value="banana" label="Banana" />
+///                 <li role="separator" class="separator"></li>
+///                 if *show_exotic {
+///                     <Fruit value="durian" label="Durian" />
+///                     <Fruit value="rambutan" label="Rambutan" />
+///                 }
+///             </Group>
+///         </Select>
+///     }
+/// }
+/// ```
+///
+/// # Behavior
+/// - The `Select` component handles single and multiple selections dynamically.
+/// - The selected values are updated using the `onchange` callback whenever the user interacts with the select options.
+/// - After `onchange`, `onselect` and `ondeselect` are called for every added and removed value, and
+///   `onchange_detail` once with both lists and the source of the change: `OptionClick` for clicked options and
+///   group headers, `Keyboard` for options picked with `Enter` or `Space`, `ChipRemove` for chip close buttons,
+///   `Clear` for form resets that change the selection, and `Programmatic` for selections restored through `persist` or `query_sync` or
+///   changed through a `SelectHandle`. Reordering chips only calls `onchange_detail`, with empty lists and the
+///   `Reorder` source. In tree mode, the values are the reported ones, so they follow `tree_output`.
+/// - `onfocus` and `onblur` fire when the focus enters the component from outside and leaves it, so moving
+///   between the `select` or trigger and the chip remove buttons fires neither. `onopen` and `onclose` follow
+///   the state of the custom popup, whether it is toggled with the pointer, the keyboard or by losing focus.
+/// - A `SelectHandle` acts on the mounted component it is passed to and does nothing otherwise. `open()` focuses
+///   the trigger and opens the custom popup, or focuses a native `select`, whose dropdown cannot be opened
+///   from code. `set_value()`, `select()` and `deselect()` ignore values without an enabled option, keep a
+///   single value in single mode, and, like `clear()`, report the change with the `Programmatic` source.
+/// - When multiple selection mode is enabled, selected values are displayed as chips with a close button to remove individual selections.
+/// - A placeholder option is displayed when no value is selected and the select is not disabled.
+/// - When `custom` is enabled, the popup opens on click or arrow keys, closes on `Escape`, `Tab` or when focus
+///   leaves the component, and keyboard navigation skips disabled options and options of collapsed groups.
+///   The headers of collapsible groups are highlighted along with the options: `Enter` or `Space` toggles them,
+///   or selects the group in tree mode, `ArrowRight` expands and `ArrowLeft` collapses them, and `ArrowLeft`
+///   on an option moves to the header of its group. The arrows follow the writing direction.
+/// - The options of the underlying `select` are kept in sync with the component state, so submitted form data
+///   always matches the displayed selection and chips, including in `multiple` and `custom` modes.
+/// - Resetting the owning form, given by `form` or the nearest ancestor form, restores `default_values`, clearing
+///   the selection by default, and emits `onchange` with the restored values.
+/// - The `validate` callback runs on every change and when the focus leaves the component. Its error is displayed
+///   below the select and marks it with `aria-invalid`, but only once the select has been changed, blurred or
+///   rejected by a form submission. Until the selection is valid, native form submission is blocked.
+/// - When `tree` is enabled, clicking a group header selects all of its enabled descendant options, or deselects
+///   them if they are all selected already, and the toggle next to it expands or collapses the group.
+/// - Every rendered element exposes its state with `data-state`, `data-selected`, `data-disabled`,
+///   `data-highlighted` and `data-placeholder` attributes, so states can be styled without the `selected_*` and
+///   `highlighted_*` props. All class props accept `Classes`.
+/// - The component renders a `dir` attribute, so browsers mirror the chips, buttons and popup in right-to-left
+///   layouts. The left and right arrow keys move the focus between chips following the visual direction.
+/// - `sort_options` and `selected_first` reorder the options within each group, in the popup, the native
+///   `select` and keyboard navigation alike, while nested groups keep their place. With `ChipOrder::Options`,
+///   the chips and the values emitted by `onchange` follow that sorted order instead of the selection order.
+/// - With `reorderable`, a chip dropped onto another one takes its place, and `Alt` with the left or right arrow
+///   key moves the focused chip one position among the visible chips, keeping the focus on it. Both emit
+///   `onreorder`, then `onchange` with the new order and `onchange_detail` with the `Reorder` source. The
+///   "+N more" button is never moved. The dragged chip and the drop target are exposed as `data-dragging` and
+///   `data-drop-target`.
+/// - Options with an `icon`, a `description` or a `badge` render a two-line layout in custom mode, with the
+///   description linked through `aria-describedby`. Native options cannot contain markup, so they show the
+///   description as their `title` tooltip and the badge in parentheses after the label.
+/// - With `searchable`, opening the popup focuses a search input in the trigger. Typing fuzzy matches the query
+///   against the labels, descriptions and keywords of the options: the characters must appear in order, and
+///   matches at the start of the label or of a word, or in consecutive characters, rank higher. Matching options
+///   are listed by decreasing relevance, outside of their groups, with the best one highlighted, and closing the
+///   popup clears the query. The query and the searched texts are normalized with `normalization` first, so by
+///   default case and accents are ignored.
+/// - The "Pinned" and "Recent" sections list copies of their options before the groups, except while searching,
+///   and only in custom mode, so that a native select submits every selected value once.
+///   Newly chosen values are moved to the front of the history on every change, and the history is written to
+///   the storage of `recent` right away, so other instances using the same key see it once they mount.
+/// - With `persist`, the selection is saved under `selectrs:` followed by the `name`, or the `id`, on every
+///   change, including form resets. On mount, the stored values without a matching option are discarded, the rest
+///   is selected and, if it differs from `default_values`, reported through `onchange`, and the cleaned list is
+///   saved back. Without a stored selection, nothing is reported. Restoring does not mark
+///   the select as touched, so validation errors stay hidden until the user interacts.
+/// - With `query_sync`, the query parameter is read on mount and whenever the location changes, replacing the
+///   selection with its values that match an option and reporting it through `onchange`. A missing parameter
+///   keeps the selection on mount, e.g. a restored one, and clears it after navigation. Changes, including form
+///   resets, write the parameter, leaving the path, the other parameters and the fragment untouched.
+/// - With `max_visible_chips`, the chips beyond the limit are replaced by a "+N more" button, which toggles
+///   between all chips and the limited list. The button and the `Count` and `CommaList` summaries are described by
+///   a `role="tooltip"` element listing every selected label, shown on hover and focus and dismissed with
+///   `Escape`.
+/// - A `floating` popup uses `position: fixed`. It flips above the trigger when there is not enough room below,
+///   aligns with the other edge or shifts when it would overflow horizontally, limits its height to the available
+///   space, and follows the trigger while the page scrolls or resizes. The placement actually used is exposed as
+///   `data-placement` on the popup.
+/// - `Option` and `Group` components find their `Select` through the `SelectContext` it provides and register
+///   with it once rendered, so they may sit at any depth below it, inside other components, fragments and
+///   conditionals. The select lists them in document order for the chips, the search and keyboard navigation.
+///   The options of a closed custom popup, of collapsed groups and of a disabled native select stay rendered
+///   but hidden, so that they remain registered. Sorting swaps the options of a group among the places they
+///   are rendered at, so markup between them keeps its place.
+///
+/// # Notes
+/// - The `children` property must contain `Option` components to populate the select dropdown, either directly,
+///   inside `Group` components or inside components of your own.
+/// - If the `multiple` property is `true`, multiple options can be selected at once. If `false`, only one option can be selected.
+/// - Custom styling can be applied to the select container, options, and labels via CSS classes or inline styles.
+#[function_component(Select)]
+pub fn select(props: &SelectProps) -> Html {
+    let SelectProps {
+        name,
+        id,
+        placeholder,
+        multiple,
+        default_values,
+        min_selected,
+        max_selected,
+        custom,
+        tree,
+        tree_output,
+        indent,
+        disabled,
+        onchange,
+        onselect,
+        ondeselect,
+        onchange_detail,
+        validate,
+        onvalidate,
+        onfocus,
+        onblur,
+        onopen,
+        onclose,
+        handle,
+        children,
+        class,
+        style,
+        labels_class,
+        labels_style,
+        label_class,
+        label_style,
+        close_class,
+        close_style,
+        select_class,
+        select_style,
+        popup_class,
+        popup_style,
+        floating,
+        portal,
+        portal_host,
+        placement,
+        popup_offset,
+        match_width,
+        error_class,
+        error_style,
+        render_chip,
+        close_icon,
+        render_placeholder,
+        dropdown_indicator,
+        prefix,
+        suffix,
+        empty_state,
+        locale,
+        dir,
+        sort_options,
+        selected_first,
+        chip_order,
+        reorderable,
+        onreorder,
+        max_visible_chips,
+        display_mode,
+        searchable,
+        normalization,
+        recent,
+        pinned,
+        persist,
+        #[cfg(feature = "router")]
+        query_sync,
+        size,
+        required,
+        form,
+        autocomplete,
+        autofocus,
+    } = props.clone();
+
+    let multiple = multiple || tree;
+    let custom = custom || tree;
+
+    let persist_key = persist_key(&persist, &name, &id);
+    #[cfg(feature = "router")]
+    let navigator = yew_router::prelude::use_navigator();
+    #[cfg(feature = "router")]
+    let location = yew_router::prelude::use_location();
+    // The selection stored by an earlier session, if any, which replaces the default values on mount.
+    let restored = {
+        let persist = persist.clone();
+        let persist_key = persist_key.clone();
+        use_state(move || persist_key.and_then(|key| persist.load(&key)))
+    };
+    let selected_values = {
+        let restored = restored.clone();
+        let default_values = default_values.clone();
+        use_state(move || (*restored).clone().unwrap_or(default_values))
+    };
+    let selected = (*selected_values).clone();
+    let open = use_state(|| false);
+    let highlighted = use_state(|| None::<Highlight>);
+    let query = use_state(String::new);
+    let history = {
+        let recent = recent.clone();
+        use_state(move || recent.load())
+    };
+    let search_ref = use_node_ref();
+    // The groups expanded or collapsed by the user since their initial state.
+    let toggled = use_state(|| Rc::new(HashSet::<Vec<usize>>::new()));
+    let registry = use_mut_ref(Registry::default);
+    let refresh = {
+        let force_update = use_force_update();
+        use_latest_callback(Callback::from(move |_| force_update.force_update()))
+    };
+    // Options register once they are rendered, so the selection is only checked against them from the
+    // second render on.
+    let ready = use_state(|| false);
+    {
+        let ready = ready.clone();
+        use_effect_with((), move |_| ready.set(true));
+    }
+    let list_id = use_list_id(&id);
+    let wrapper_ref = use_node_ref();
+    let select_ref = use_node_ref();
+    let trigger_ref = use_node_ref();
+    let popup_ref = use_node_ref();
+    let touched = use_state(|| false);
+    let theme = use_theme();
+    let locale = use_locale(locale);
+    let direction = use_direction(dir);
+    let chips_ref = use_node_ref();
+    let chips_expanded = use_state(|| false);
+    let summary_open = use_state(|| false);
+    let dragged = use_state(|| None::<usize>);
+    let drop_target = use_state(|| None::<usize>);
+    let focus_after_move = use_mut_ref(|| None::<u32>);
+    let reorderable = reorderable
+        && multiple
+        && !disabled
+        && chip_order == ChipOrder::Selection
+        && !(tree && tree_output == TreeOutput::Ancestors);
+
+    // Keeps the focus on a chip moved with the keyboard once it is rendered at its new position.
+    {
+        let chips_ref = chips_ref.clone();
+        let focus_after_move = focus_after_move.clone();
+        use_effect(move || {
+            let index = focus_after_move.borrow_mut().take();
+            if let Some(chip) =
+                index.and_then(|index| chips_ref.cast::<Element>()?.children().item(index))
+            {
+                focus_chip(&chip);
+            }
+        });
+    }
+    let order = OptionOrder {
+        sort: &sort_options,
+        selected_first,
+        selected: &selected,
+        locale: &locale,
+    };
+    // Registrations made from now on call for another render.
+    registry.borrow_mut().pending = false;
+    let registered = document_order(&registry.borrow().options);
+    // The options keep their identity while they are unchanged, so that the context does not change with every
+    // render.
+    let (entries, shown) = {
+        let cache = use_mut_ref(|| (Rc::new(Vec::<OptionEntry>::new()), Rc::new(HashMap::new())));
+        let entries = display_order(&registered, &order);
+        if !same_entries(&cache.borrow().0, &entries) {
+            let shown = entries
+                .iter()
+                .map(|entry| (entry.id, entry.props.clone()))
+                .collect();
+            *cache.borrow_mut() = (Rc::new(entries), Rc::new(shown));
+        }
+        let cached = cache.borrow().clone();
+        cached
+    };
+    // The options in sorted order, regardless of the selection, which the chips follow in `ChipOrder::Options`.
+    let ranked: Vec<String> = display_order(
+        &registered,
+        &OptionOrder {
+            selected_first: false,
+            ..order
+        },
+    )
+    .iter()
+    .map(|entry| entry.props.value.to_string())
+    .collect();
+    let group_entries = {
+        let mut groups = registry.borrow().groups.clone();
+        // Nested groups register before their parents, which must be listed first.
+        groups.sort_by_key(|(path, _)| path.len());
+        Rc::new(groups)
+    };
+    let collapsed: Rc<HashSet<Vec<usize>>> = Rc::new(
+        group_entries
+            .iter()
+            .filter(|(path, group)| {
+                (group.collapsible || tree) && group.default_collapsed != toggled.contains(path)
+            })
+            .map(|(path, _)| path.clone())
+            .collect(),
+    );
+    let searchable = searchable && custom;
+    let results = (searchable && !query.trim().is_empty())
+        .then(|| search_options(&entries, &query, normalization, &locale));
+    let floating = floating || portal;
+    let position = use_floating(
+        trigger_ref.clone(),
+        popup_ref.clone(),
+        FloatingOptions {
+            active: custom && floating && *open && !disabled,
+            placement,
+            direction,
+            offset: popup_offset,
+            match_width,
+            revision: selected.len(),
+        },
+    );
+
+    // Items reachable with the keyboard, in display order.
+    let navigable: Vec<Highlight> = match &results {
+        Some(results) => results
+            .iter()
+            .filter(|result| !result.props.disabled)
+            .map(|result| Highlight::Value(result.props.value.to_string()))
+            .collect(),
+        None => navigable_items(&entries, &group_entries, &collapsed, tree),
+    };
+
+    // Values reported through `onchange` and displayed as chips.
+    let report = {
+        let entries = entries.clone();
+        let group_entries = group_entries.clone();
+        move |values: &[String]| {
+            if tree && tree_output == TreeOutput::Ancestors {
+                highest_selected(&entries, &group_entries, values)
+            } else {
+                values.to_vec()
+            }
+        }
+    };
+
+    // Checks the limits on the number of values before the custom validator.
+    let validate = {
+        let locale = locale.clone();
+        Callback::from(move |values: Vec<String>| {
+            check_limits(values.len(), min_selected, max_selected, &locale)
+                .and_then(|_| validate.emit(values))
+        })
+    };
+    let validation = validate.emit(report(&selected));
+    let error = validation.clone().err().filter(|_| *touched);
+
+    // Stores new selected values, validates them and notifies the parent.
+    let update = {
+        let selected_values = selected_values.clone();
+        let touched = touched.clone();
+        let on_change = onchange.clone();
+        let validate = validate.clone();
+        let onvalidate = onvalidate.clone();
+        let report = report.clone();
+        let history = history.clone();
+        let recent = recent.clone();
+        let persist = persist.clone();
+        let persist_key = persist_key.clone();
+        #[cfg(feature = "router")]
+        let (navigator, location, query_sync) =
+            (navigator.clone(), location.clone(), query_sync.clone());
+        Callback::from(move |mut values: Vec<String>| {
+            let chosen: Vec<String> = values
+                .iter()
+                .filter(|value| !selected_values.contains(value))
+                .cloned()
+                .collect();
+            if recent.size > 0 && !chosen.is_empty() {
+                history.set(recent.record(&history, &chosen));
+            }
+            if chip_order == ChipOrder::Options {
+                // Values without a matching option keep their relative order at the end.
+                values.sort_by_key(|value| {
+                    ranked.iter().position(|v| v == value).unwrap_or(usize::MAX)
+                });
+            }
+            if let Some(key) = &persist_key {
+                persist.save(key, &values);
+            }
+            #[cfg(feature = "router")]
+            if let (Some(navigator), Some(location)) = (&navigator, &location) {
+                query_sync.write(navigator, location, &values);
+            }
+            let reported = report(&values);
+            selected_values.set(values);
+            touched.set(true);
+            onvalidate.emit(validate.emit(reported.clone()));
+            on_change.emit(reported);
+        })
+    };
+
+    // Notifies the granular callbacks of the values added and removed between two selections.
+    let notify = {
+        let report = report.clone();
+        let onchange_detail = onchange_detail.clone();
+        move |old: &[String], new: &[String], source: ChangeSource| {
+            let change = SelectionChange::between(&report(old), &report(new), source);
+            for value in &change.added {
+                onselect.emit(value.clone());
+            }
+            for value in &change.removed {
+                ondeselect.emit(value.clone());
+            }
+            if !change.added.is_empty() || !change.removed.is_empty() {
+                onchange_detail.emit(change);
+            }
+        }
+    };
+
+    // Updates the selection on behalf of the user, reporting what was added and removed.
+    let change = {
+        let selected_values = selected_values.clone();
+        let update = update.clone();
+        let notify = notify.clone();
+        let report = report.clone();
+        Callback::from(move |(values, source): (Vec<String>, ChangeSource)| {
+            let old = (*selected_values).clone();
+            // Additions beyond the limit are ignored, while removals stay possible.
+            let count = report(&values).len();
+            if values.len() > old.len() && max_selected.is_some_and(|max| count > max) {
+                return;
+            }
+            update.emit(values.clone());
+            notify(&old, &values, source);
+        })
+    };
+
+    let handle_group_change = {
+        let selected_values = selected_values.clone();
+        let open = open.clone();
+        let highlighted = highlighted.clone();
+        let change = change.clone();
+        let trigger_ref = trigger_ref.clone();
+        Callback::from(move |(value, source): (String, ChangeSource)| {
+            let mut current_values = (*selected_values).clone();
+            if multiple {
+                if current_values.contains(&value) {
+                    current_values.retain(|v| v != &value);
+                } else {
+                    current_values.push(value.clone());
+                }
+            } else {
+                current_values = vec![value.clone()];
+                focus_trigger(&trigger_ref);
+                open.set(false);
+            }
+            highlighted.set(Some(Highlight::Value(value)));
+            change.emit((current_values, source));
+        })
+    };
+
+    let remove_chip = {
+        let selected_values = selected_values.clone();
+        let change = change.clone();
+        let entries = entries.clone();
+        let group_entries = group_entries.clone();
+        Callback::from(move |value: String| {
+            // A chip may stand for a whole group in tree mode, in which case all of
+            // its options are removed.
+            let removed: Vec<String> = match group_entries
+                .iter()
+                .find(|(_, group)| tree && group_value(group) == value)
+            {
+                Some((path, _)) => option_values(&entries, path, true),
+                None => vec![value],
+            };
+            let mut current_values = (*selected_values).clone();
+            current_values.retain(|v| !removed.contains(v));
+            change.emit((current_values, ChangeSource::ChipRemove));
+        })
+    };
+
+    // Moves the chip at the first position to the second one, shifting the chips in between.
+    let move_chip = {
+        let selected_values = selected_values.clone();
+        let update = update.clone();
+        let onchange_detail = onchange_detail.clone();
+        Callback::from(move |(from, to): (usize, usize)| {
+            let mut current_values = (*selected_values).clone();
+            if from == to || from >= current_values.len() || to >= current_values.len() {
+                return;
+            }
+            let value = current_values.remove(from);
+            current_values.insert(to, value.clone());
+            onreorder.emit(ChipReorder { value, from, to });
+            update.emit(current_values);
+            onchange_detail.emit(SelectionChange {
+                added: Vec::new(),
+                removed: Vec::new(),
+                source: ChangeSource::Reorder,
+            });
+        })
+    };
+
+    let ondragstart = {
+        let dragged = dragged.clone();
+        Callback::from(move |(index, event): (usize, DragEvent)| {
+            if !reorderable {
+                return;
+            }
+            if let Some(transfer) = event.data_transfer() {
+                transfer.set_effect_allowed("move");
+                // Some browsers only start dragging when data is set.
+                let _ = transfer.set_data("text/plain", "");
+            }
+            dragged.set(Some(index));
+        })
+    };
+
+    let ondragover = {
+        let dragged = dragged.clone();
+        let drop_target = drop_target.clone();
+        Callback::from(move |(index, event): (usize, DragEvent)| {
+            if dragged.is_some() {
+                event.prevent_default();
+                if *drop_target != Some(index) {
+                    drop_target.set(Some(index));
+                }
+            }
+        })
+    };
+
+    let ondrop = {
+        let dragged = dragged.clone();
+        let drop_target = drop_target.clone();
+        let move_chip = move_chip.clone();
+        Callback::from(move |(index, event): (usize, DragEvent)| {
+            if let Some(from) = *dragged {
+                event.prevent_default();
+                move_chip.emit((from, index));
+            }
+            dragged.set(None);
+            drop_target.set(None);
+        })
+    };
+
+    let ondragend = {
+        let dragged = dragged.clone();
+        let drop_target = drop_target.clone();
+        Callback::from(move |_: DragEvent| {
+            dragged.set(None);
+            drop_target.set(None);
+        })
+    };
+
+    let select_group = {
+        let selected_values = selected_values.clone();
+        let change = change.clone();
+        let entries = entries.clone();
+        Callback::from(move |(path, source): (Vec<usize>, ChangeSource)| {
+            let values = option_values(&entries, &path, false);
+            let mut current_values = (*selected_values).clone();
+            if values.iter().all(|value| current_values.contains(value)) {
+                current_values.retain(|v| !values.contains(v));
+            } else {
+                for value in values {
+                    if !current_values.contains(&value) {
+                        current_values.push(value);
+                    }
+                }
+            }
+            change.emit((current_values, source));
+        })
+    };
+    let select_group = use_latest_callback(select_group);
+
+    let toggle_group = {
+        let toggled = toggled.clone();
+        let group_entries = group_entries.clone();
+        use_latest_callback(Callback::from(move |path: Vec<usize>| {
+            let mut current = (**toggled).clone();
+            if !current.remove(&path) {
+                current.insert(path.clone());
+            }
+            if let Some((_, group)) = group_entries.iter().find(|(p, _)| *p == path) {
+                group
+                    .ontoggle
+                    .emit(group.default_collapsed != current.contains(&path));
+            }
+            toggled.set(Rc::new(current));
+        }))
+    };
+
+    let toggle_open = toggle_popup(&open, disabled);
+
+    // Shows the tooltip describing the full selection, or hides it on `false`.
+    let show_summary = {
+        let summary_open = summary_open.clone();
+        Callback::from(move |show: bool| summary_open.set(show))
+    };
+    let onsummarykeydown = {
+        let summary_open = summary_open.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if event.key() == "Escape" {
+                summary_open.set(false);
+            }
+        })
+    };
+
+    let toggle_chips = {
+        let chips_expanded = chips_expanded.clone();
+        Callback::from(move |_: MouseEvent| chips_expanded.set(!*chips_expanded))
+    };
+
+    let chip_values = report(&selected);
+    let collapsible = max_visible_chips.is_some_and(|max| chip_values.len() > max);
+    let visible_chips = match max_visible_chips {
+        Some(max) if !*chips_expanded => max,
+        _ => chip_values.len(),
+    };
+    // The number of chips rendered before the "+N more" button, if any.
+    let rendered_chips = visible_chips.min(chip_values.len());
+
+    // Moves the focus between chips with the arrow keys, following the visual direction, or the focused chip
+    // itself when `Alt` is pressed.
+    let onchipkeydown = {
+        let chips_ref = chips_ref.clone();
+        let focus_after_move = focus_after_move.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            let step = match logical_arrow(event.key(), direction).as_str() {
+                "ArrowRight" => 1,
+                "ArrowLeft" => -1,
+                _ => return,
+            };
+            let Some((chips, index)) = focused_chip(&event, &chips_ref) else {
+                return;
+            };
+            if event.alt_key() {
+                // Only chips move, never onto or from the "+N more" button after them.
+                let in_chips = |index: u32| (index as usize) < rendered_chips;
+                let Some(to) = index
+                    .checked_add_signed(step)
+                    .filter(|&to| reorderable && in_chips(index) && in_chips(to))
+                else {
+                    return;
+                };
+                event.prevent_default();
+                *focus_after_move.borrow_mut() = Some(to);
+                move_chip.emit((index as usize, to as usize));
+                return;
+            }
+            if let Some(chip) = index
+                .checked_add_signed(step)
+                .and_then(|next| chips.item(next))
+            {
+                event.prevent_default();
+                focus_chip(&chip);
+            }
+        })
+    };
+
+    let onkeydown = {
+        let open = open.clone();
+        let highlighted = highlighted.clone();
+        let handle_group_change = handle_group_change.clone();
+        let select_group = select_group.clone();
+        let toggle_group = toggle_group.clone();
+        let trigger_ref = trigger_ref.clone();
+        let entries = entries.clone();
+        let collapsed = collapsed.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if disabled {
+                return;
+            }
+            let key = logical_arrow(event.key(), direction);
+            match key.as_str() {
+                // Typing a space or moving the caret in the search input.
+                " " | "Home" | "End" if searchable && *open => {}
+                "ArrowDown" | "ArrowUp" | "Home" | "End" => {
+                    event.prevent_default();
+                    let current = (*highlighted)
+                        .as_ref()
+                        .and_then(|item| navigable.iter().position(|i| i == item));
+                    if let Some(next) = step_highlight(&key, current, navigable.len()) {
+                        open.set(true);
+                        highlighted.set(navigable.get(next).cloned());
+                    }
+                }
+                "Enter" | " " => {
+                    event.prevent_default();
+                    match (*open, (*highlighted).clone()) {
+                        (true, Some(item)) if navigable.contains(&item) => match item {
+                            Highlight::Value(value) => {
+                                handle_group_change.emit((value, ChangeSource::Keyboard))
+                            }
+                            Highlight::Group(path) if tree => {
+                                select_group.emit((path, ChangeSource::Keyboard))
+                            }
+                            Highlight::Group(path) => toggle_group.emit(path),
+                        },
+                        _ => open.set(!*open),
+                    }
+                }
+                // Expanding or collapsing the highlighted group, or moving from an option to the header of its
+                // group.
+                "ArrowLeft" | "ArrowRight" if *open => match (*highlighted).clone() {
+                    Some(Highlight::Group(path))
+                        if collapsed.contains(&path) == (key == "ArrowRight") =>
+                    {
+                        event.prevent_default();
+                        toggle_group.emit(path);
+                    }
+                    Some(Highlight::Value(value)) if key == "ArrowLeft" => {
+                        let header = entries
+                            .iter()
+                            .find(|entry| *entry.props.value == *value)
+                            .and_then(|entry| {
+                                (1..=entry.path.len())
+                                    .rev()
+                                    .map(|len| Highlight::Group(entry.path[..len].to_vec()))
+                                    .find(|header| navigable.contains(header))
+                            });
+                        if let Some(header) = header {
+                            event.prevent_default();
+                            highlighted.set(Some(header));
+                        }
+                    }
+                    _ => {}
+                },
+                _ => close_popup(&event, &open, &trigger_ref),
+            }
+        })
+    };
+
+    let onsearchinput = {
+        let query = query.clone();
+        let highlighted = highlighted.clone();
+        let entries = entries.clone();
+        let locale = locale.clone();
+        Callback::from(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlInputElement>().value();
+            // Highlights the best result, so that `Enter` picks it.
+            highlighted.set(
+                search_options(&entries, &value, normalization, &locale)
+                    .into_iter()
+                    .find(|result| !result.props.disabled)
+                    .map(|result| Highlight::Value(result.props.value.to_string())),
+            );
+            query.set(value);
+        })
+    };
+
+    // Focuses the search input when the popup opens, and clears the query when it closes.
+    {
+        let query = query.clone();
+        let search_ref = search_ref.clone();
+        use_effect_with(*open, move |open| {
+            if !*open && !query.is_empty() {
+                query.set(String::new());
+            } else if let Some(input) = search_ref.cast::<HtmlElement>() {
+                let _ = input.focus();
+            }
+        });
+    }
+
+    // Reports the popup opening and closing, but not its initial state.
+    {
+        let mounted = use_mut_ref(|| false);
+        use_effect_with(*open, move |open| {
+            if mounted.replace(true) {
+                if *open {
+                    onopen.emit(());
+                } else {
+                    onclose.emit(());
+                }
+            }
+        });
+    }
+
+    let onfocusin = {
+        let wrapper_ref = wrapper_ref.clone();
+        Callback::from(move |event: FocusEvent| {
+            if focus_crosses(&event, &wrapper_ref) {
+                onfocus.emit(event);
+            }
+        })
+    };
+
+    let onfocusout = {
+        let open = open.clone();
+        let wrapper_ref = wrapper_ref.clone();
+        let touched = touched.clone();
+        let validation = validation.clone();
+        Callback::from(move |event: FocusEvent| {
+            if focus_crosses(&event, &wrapper_ref) {
+                open.set(false);
+                touched.set(true);
+                onvalidate.emit(validation.clone());
+                onblur.emit(event);
+            }
+        })
+    };
+
+    let onselectchange = {
+        let handle_group_change = handle_group_change.clone();
+        Callback::from(move |event: Event| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            if !multiple && !value.is_empty() {
+                handle_group_change.emit((value, ChangeSource::OptionClick));
+            }
+        })
+    };
+
+    let reset = {
+        let selected_values = selected_values.clone();
+        let touched = touched.clone();
+        let open = open.clone();
+        let highlighted = highlighted.clone();
+        let on_change = onchange.clone();
+        let report = report.clone();
+        let notify = notify.clone();
+        let persist = persist.clone();
+        let persist_key = persist_key.clone();
+        #[cfg(feature = "router")]
+        let (navigator, location, query_sync) =
+            (navigator.clone(), location.clone(), query_sync.clone());
+        let defaults = valid_values(&entries, default_values.clone());
+        Callback::from(move |_| {
+            let old = (*selected_values).clone();
+            selected_values.set(defaults.clone());
+            touched.set(false);
+            open.set(false);
+            highlighted.set(None);
+            if old == defaults {
+                return;
+            }
+            if let Some(key) = &persist_key {
+                persist.save(key, &defaults);
+            }
+            #[cfg(feature = "router")]
+            if let (Some(navigator), Some(location)) = (&navigator, &location) {
+                query_sync.write(navigator, location, &defaults);
+            }
+            on_change.emit(report(&defaults));
+            notify(&old, &defaults, ChangeSource::Clear);
+        })
+    };
+    use_form_reset(wrapper_ref.clone(), form, reset);
+
+    // Applies the commands sent through the handle. Several commands may be sent before the next render, so
+    // the selection they change is tracked in a cell rather than read from the state.
+    {
+        let latest = use_mut_ref(Vec::new);
+        *latest.borrow_mut() = selected.clone();
+        let open = open.clone();
+        let trigger_ref = trigger_ref.clone();
+        let select_ref = select_ref.clone();
+        let update = update.clone();
+        let notify = notify.clone();
+        let enabled: HashSet<String> = entries
+            .iter()
+            .filter(|entry| !entry.props.disabled)
+            .map(|entry| entry.props.value.to_string())
+            .collect();
+        handle.attach(Callback::from(move |command: SelectCommand| {
+            let old = latest.borrow().clone();
+            let new = match command {
+                SelectCommand::Open | SelectCommand::Focus => {
+                    if custom {
+                        focus_trigger(&trigger_ref);
+                    } else if let Some(select) = select_ref.cast::<HtmlElement>() {
+                        let _ = select.focus();
+                    }
+                    if matches!(command, SelectCommand::Open) && custom && !disabled {
+                        open.set(true);
+                    }
+                    return;
+                }
+                SelectCommand::Close => {
+                    open.set(false);
+                    return;
+                }
+                SelectCommand::Clear => Vec::new(),
+                SelectCommand::SetValue(requested) => {
+                    let mut values: Vec<String> = Vec::new();
+                    for value in requested {
+                        if enabled.contains(&value) && !values.contains(&value) {
+                            values.push(value);
+                        }
+                    }
+                    values.truncate(if multiple {
+                        max_selected.unwrap_or(usize::MAX)
+                    } else {
+                        1
+                    });
+                    values
+                }
+                SelectCommand::Select(value) if !enabled.contains(&value) => return,
+                SelectCommand::Select(value)
+                    if multiple
+                        && !old.contains(&value)
+                        && max_selected.is_some_and(|max| old.len() >= max) =>
+                {
+                    return
+                }
+                SelectCommand::Select(value) if multiple => {
+                    let mut values = old.clone();
+                    if !values.contains(&value) {
+                        values.push(value);
+                    }
+                    values
+                }
+                SelectCommand::Select(value) => vec![value],
+                SelectCommand::Deselect(value) => {
+                    old.iter().filter(|v| **v != value).cloned().collect()
+                }
+            };
+            if new != old {
+                *latest.borrow_mut() = new.clone();
+                update.emit(new.clone());
+                notify(&old, &new, ChangeSource::Programmatic);
+            }
+        }));
+    }
+    {
+        let handle = handle.clone();
+        use_effect_with(handle, |handle| {
+            let handle = handle.clone();
+            move || handle.detach()
+        });
+    }
+
+    // Discards the restored or default values without a registered option once the options are registered,
+    // then saves the selection back and reports a restored selection that differs from the default values.
+    {
+        let selected_values = selected_values.clone();
+        let on_change = onchange.clone();
+        let report = report.clone();
+        let notify = notify.clone();
+        let entries = entries.clone();
+        let persist = persist.clone();
+        let persist_key = persist_key.clone();
+        let default_values = default_values.clone();
+        use_effect_with(*ready, move |ready| {
+            if !*ready {
+                return;
+            }
+            let values = valid_values(&entries, (*selected_values).clone());
+            if values != *selected_values {
+                selected_values.set(values.clone());
+            }
+            if let Some(key) = &persist_key {
+                persist.save(key, &values);
+            }
+            let defaults = valid_values(&entries, default_values);
+            if restored.is_some() && values != defaults {
+                on_change.emit(report(&values));
+                notify(&defaults, &values, ChangeSource::Programmatic);
+            }
+        });
+    }
+
+    // Selects the values of the bound query parameter on mount and whenever the location changes.
+    #[cfg(feature = "router")]
+    {
+        let selected_values = selected_values.clone();
+        let on_change = onchange.clone();
+        let report = report.clone();
+        let entries = entries.clone();
+        let mounted = use_mut_ref(|| false);
+        let synced = location
+            .as_ref()
+            .and_then(|location| query_sync.read(location));
+        use_effect_with((synced, *ready), move |(synced, ready)| {
+            if !*ready {
+                return;
+            }
+            let first = !mounted.replace(true);
+            let values = match synced {
+                Some(values) => valid_values(&entries, values.clone()),
+                None if first => return,
+                None => Vec::new(),
+            };
+            if values != *selected_values {
+                if let Some(key) = &persist_key {
+                    persist.save(key, &values);
+                }
+                on_change.emit(report(&values));
+                notify(&selected_values, &values, ChangeSource::Programmatic);
+                selected_values.set(values);
+            }
+        });
+    }
+
+    // Keeps the selectedness of the options in the DOM in sync with the component state and the rendered
+    // options, so that submitted form data always matches the displayed selection.
+    {
+        let select_ref = select_ref.clone();
+        let rendered: Vec<AttrValue> = entries
+            .iter()
+            .map(|entry| entry.props.value.clone())
+            .collect();
+        use_effect_with(
+            (report(&selected), custom, disabled, rendered),
+            move |(values, _, disabled, _)| {
+                if let Some(select) = select_ref.cast::<HtmlSelectElement>() {
+                    let options = select.options();
+                    for index in 0..options.length() {
+                        if let Some(option) = options
+                            .item(index)
+                            .and_then(|option| option.dyn_into::<HtmlOptionElement>().ok())
+                        {
+                            let value = option.value();
+                            option.set_selected(if value.is_empty() {
+                                values.is_empty() || *disabled
+                            } else {
+                                !*disabled && values.contains(&value)
+                            });
+                        }
+                    }
+                }
+            },
+        );
+    }
+
+    // Shows the error once the browser rejects a form submission because of this select.
+    let oninvalid = {
+        let touched = touched.clone();
+        Callback::from(move |_: Event| touched.set(true))
+    };
+
+    {
+        let select_ref = select_ref.clone();
+        use_effect_with(validation.clone(), move |validation| {
+            if let Some(select) = select_ref.cast::<HtmlSelectElement>() {
+                select.set_custom_validity(validation.as_ref().err().map_or("", String::as_str));
+            }
+        });
+    }
+
+    {
+        let list_id = (*list_id).clone();
+        use_effect_with(
+            ((*highlighted).clone(), *open),
+            move |(highlighted, open)| {
+                if let (true, Some(item)) = (*open, highlighted) {
+                    if let Some(element) = web_sys::window()
+                        .and_then(|window| window.document())
+                        .and_then(|document| document.get_element_by_id(&item.id(&list_id)))
+                    {
+                        let options = ScrollIntoViewOptions::new();
+                        options.set_block(ScrollLogicalPosition::Nearest);
+                        element.scroll_into_view_with_scroll_into_view_options(&options);
+                    }
+                }
+            },
+        );
+    }
+
+    // Prepares a copy of an option rendered outside of its group, in the search results or a section.
+    let detached_option = |props: &OptionProps, id: String, highlighted: bool| {
+        let mut props = props.clone();
+        let value = props.value.clone();
+        props.selected = selected.iter().any(|v| *v == value);
+        props.highlighted = highlighted;
+        props.custom = custom;
+        props.tree = tree;
+        props.depth = 0;
+        props.id = id;
+        props.on_click =
+            handle_group_change.reform(move |_| (value.to_string(), ChangeSource::OptionClick));
+        props
+    };
+    let section_options = |values: &[String]| -> Vec<Rc<OptionProps>> {
+        values
+            .iter()
+            .filter_map(|value| entries.iter().find(|entry| entry.props.value == value))
+            .map(|entry| entry.props.clone())
+            .collect()
+    };
+    let recent_values: Vec<String> = history
+        .iter()
+        .filter(|value| !pinned.contains(value))
+        .take(recent.size)
+        .cloned()
+        .collect();
+    let sections = [
+        ("pinned", locale.pinned, section_options(&pinned)),
+        ("recent", locale.recent, section_options(&recent_values)),
+    ];
+    // A native select would submit the copies listed in the sections along with the originals, so they are only
+    // rendered in the custom listbox.
+    let sections = sections
+        .into_iter()
+        .filter(|(_, _, options)| custom && !options.is_empty())
+        .map(|(section, label, options)| {
+            let options = options.iter().map(|props| {
+                let id = format!("{}-{}", option_id(&list_id, &props.value), section);
+                let key = props.value.to_string();
+                let props = detached_option(props, id, false);
+                html! { <DetachedOption key={key} ..props /> }
+            });
+            html! {
+                <li role="group" aria-label={label} class="selectrs-group selectrs-section" data-section={section}>
+                    <div class="selectrs-group-header">{ label }</div>
+                    <ul class="selectrs-group-options" role="none">
+                        { for options }
+                    </ul>
+                </li>
+            }
+        })
+        .collect::<Vec<Html>>();
+
+    let select_option =
+        use_latest_callback(handle_group_change.reform(|value| (value, ChangeSource::OptionClick)));
+    let context = SelectContext {
+        selected: Rc::new(selected.clone()),
+        highlighted: match &*highlighted {
+            Some(Highlight::Value(value)) => Some(value.clone()),
+            _ => None,
+        },
+        highlighted_group: match &*highlighted {
+            Some(Highlight::Group(path)) => Some(path.clone()),
+            _ => None,
+        },
+        multiple,
+        custom,
+        tree,
+        depth: 0,
+        path: Vec::new(),
+        // The options stay rendered while search results are listed, or while a native select is disabled,
+        // so that they remain registered.
+        hidden: if custom { results.is_some() } else { disabled },
+        indent,
+        list_id: (*list_id).clone(),
+        select: select_option,
+        // A native single select reports its value through its own `change` event instead,
+        // since browsers do not reliably fire click events on dropdown options.
+        clickable: custom || multiple,
+        entries: entries.clone(),
+        shown,
+        toggled: (*toggled).clone(),
+        toggle_group,
+        select_group,
+        registry,
+        refresh,
+    };
+    let options = html! {
+        <ContextProvider<SelectContext> context={context}>
+            { children }
+        </ContextProvider<SelectContext>>
+    };
+
+    let trigger_label = selected_label(&entries, &selected).filter(|_| !multiple);
+    let slot_context = SlotContext {
+        open: *open,
+        disabled,
+        multiple,
+        placeholder: placeholder.clone(),
+        selected: report(&selected),
+    };
+    let summary_id = summary_id(&list_id);
+    let summary = chip_values
+        .iter()
+        .map(|value| value_text(&entries, &group_entries, value))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let has_summary =
+        multiple && !chip_values.is_empty() && (collapsible || display_mode != DisplayMode::Chips);
+    let render_slot = |slot: &std::option::Option<Callback<SlotContext, Html>>| {
+        slot.as_ref().map(|slot| slot.emit(slot_context.clone()))
+    };
+    let placeholder_content =
+        render_slot(&render_placeholder).unwrap_or_else(|| html! { placeholder.clone() });
+    let prefix = render_slot(&prefix)
+        .map(|prefix| html! { <span class="selectrs-prefix">{ prefix }</span> });
+    let suffix = render_slot(&suffix)
+        .map(|suffix| html! { <span class="selectrs-suffix">{ suffix }</span> });
+
+    html! {
+        <div
+            class={classes!("selectrs", class)}
+            style={style}
+            ref={wrapper_ref}
+            dir={direction.as_str()}
+            onfocusin={onfocusin}
+            onfocusout={onfocusout}
+            data-state={open_state(*open)}
+            data-disabled={data_flag(disabled)}
+            data-placeholder={data_flag(selected.is_empty())}
+        >
+            { if multiple && display_mode != DisplayMode::Chips {
+                html! {
+                    if !chip_values.is_empty() {
+                        <div
+                            class={classes!("selectrs-summary", labels_class)}
+                            style={labels_style}
+                            tabindex="0"
+                            aria-describedby={summary_id.clone()}
+                            data-disabled={data_flag(disabled)}
+                            onmouseenter={show_summary.reform(|_| true)}
+                            onmouseleave={show_summary.reform(|_| false)}
+                            onfocus={show_summary.reform(|_| true)}
+                            onblur={show_summary.reform(|_| false)}
+                            onkeydown={onsummarykeydown.clone()}
+                        >
+                            if display_mode == DisplayMode::Count {
+                                { SelectLocale::fill(locale.selected_count, chip_values.len()) }
+                            } else {
+                                { for chip_values.iter().enumerate().map(|(index, value)| html! {
+                                    <>
+                                        if index > 0 {
+                                            { ", " }
+                                        }
+                                        { value_label(&entries, &group_entries, value) }
+                                    </>
+                                }) }
+                            }
+                        </div>
+                    }
+                }
+            } else if multiple {
+                html! {
+                    <div
+                        ref={chips_ref}
+                        class={classes!("selectrs-chips", labels_class)}
+                        style={labels_style}
+                        data-disabled={data_flag(disabled)}
+                        onkeydown={onchipkeydown}
+                    >
+                        { for chip_values.iter().take(visible_chips).cloned().enumerate().map(|(index, value)| html! {
+                            <div
+                                key={value.clone()}
+                                class={classes!("selectrs-chip", label_class.clone())}
+                                style={label_style}
+                                tabindex="-1"
+                                draggable={reorderable.then_some("true")}
+                                aria-keyshortcuts={reorderable.then_some("Alt+ArrowLeft Alt+ArrowRight")}
+                                data-disabled={data_flag(disabled)}
+                                data-dragging={data_flag(*dragged == Some(index))}
+                                data-drop-target={data_flag(*dragged != Some(index) && *drop_target == Some(index))}
+                                ondragstart={ondragstart.reform(move |event| (index, event))}
+                                ondragover={ondragover.reform(move |event| (index, event))}
+                                ondrop={ondrop.reform(move |event| (index, event))}
+                                ondragend={ondragend.clone()}
+                            >
+                                if let Some(render_chip) = &render_chip {
+                                    { render_chip.emit(ChipContext {
+                                        label: value_label(&entries, &group_entries, &value),
+                                        index,
+                                        disabled,
+                                        remove: remove_chip.reform({
+                                            let value = value.clone();
+                                            move |_| value.clone()
+                                        }),
+                                        value,
+                                    }) }
+                                } else {
+                                    { value.clone() }
+                                    <button
+                                        type="button"
+                                        class={classes!("selectrs-chip-remove", close_class.clone())}
+                                        style={close_style}
+                                        data-disabled={data_flag(disabled)}
+                                        aria-label={SelectLocale::fill(locale.remove, &value)}
+                                        onclick={remove_chip.reform(move |_| value.clone())}
+                                    >
+                                        { close_icon.clone() }
+                                    </button>
+                                }
+                            </div>
+                        }) }
+                        if collapsible {
+                            <button
+                                type="button"
+                                class={classes!("selectrs-chip", "selectrs-chip-more", label_class.clone())}
+                                style={label_style}
+                                aria-expanded={chips_expanded.to_string()}
+                                aria-describedby={summary_id.clone()}
+                                data-state={if *chips_expanded { "expanded" } else { "collapsed" }}
+                                onclick={toggle_chips}
+                                onmouseenter={show_summary.reform(|_| true)}
+                                onmouseleave={show_summary.reform(|_| false)}
+                                onfocus={show_summary.reform(|_| true)}
+                                onblur={show_summary.reform(|_| false)}
+                                onkeydown={onsummarykeydown.clone()}
+                            >
+                                if *chips_expanded {
+                                    { locale.show_less }
+                                } else {
+                                    { SelectLocale::fill(locale.more, chip_values.len() - visible_chips) }
+                                }
+                            </button>
+                        }
+                    </div>
+                }
+            } else {
+                html! {}
+            } }
+            if has_summary {
+                <div id={summary_id} role="tooltip" class="selectrs-tooltip" hidden={!*summary_open}>
+                    { summary }
+                </div>
+            }
+            if custom {
+                <div
+                    ref={trigger_ref}
+                    id={id}
+                    role="combobox"
+                    tabindex={if disabled { "-1" } else { "0" }}
+                    class={classes!("selectrs-trigger", select_class)}
+                    style={select_style}
+                    aria-haspopup="listbox"
+                    aria-expanded={open.to_string()}
+                    aria-controls={(*list_id).clone()}
+                    aria-disabled={disabled.to_string()}
+                    aria-required={required.to_string()}
+                    aria-invalid={error.is_some().then_some("true")}
+                    aria-describedby={error.is_some().then(|| error_id(&list_id))}
+                    aria-activedescendant={(*highlighted).as_ref().filter(|_| *open).map(|item| item.id(&list_id))}
+                    autofocus={autofocus}
+                    onclick={toggle_open}
+                    onkeydown={onkeydown}
+                    data-state={open_state(*open)}
+                    data-disabled={data_flag(disabled)}
+                    data-placeholder={data_flag(trigger_label.is_none())}
+                >
+                    { prefix }
+                    <span class="selectrs-value">
+                        if searchable && *open {
+                            <input
+                                ref={search_ref}
+                                type="search"
+                                class="selectrs-search"
+                                value={(*query).clone()}
+                                placeholder={locale.search_placeholder}
+                                autocomplete="off"
+                                aria-label={locale.search_placeholder}
+                                aria-autocomplete="list"
+                                aria-controls={(*list_id).clone()}
+                                aria-activedescendant={(*highlighted).as_ref().map(|item| item.id(&list_id))}
+                                oninput={onsearchinput}
+                                onclick={|event: MouseEvent| event.stop_propagation()}
+                            />
+                        } else {
+                            { trigger_label.unwrap_or(placeholder_content) }
+                        }
+                    </span>
+                    { suffix }
+                    if let Some(indicator) = render_slot(&dropdown_indicator) {
+                        <span class="selectrs-indicator" aria-hidden="true">{ indicator }</span>
+                    }
+                </div>
+                // The popup is only hidden when closed, so that its options remain registered.
+                { floating_portal(portal, portal_host, html! {
+                    <ul
+                        ref={popup_ref}
+                        id={(*list_id).clone()}
+                        role={if tree { "tree" } else { "listbox" }}
+                        aria-multiselectable={multiple.to_string()}
+                        class={classes!("selectrs-popup", popup_class)}
+                        style={if !*open || disabled {
+                            format!("{} display: none;", popup_style)
+                        } else if floating {
+                            floating_style(popup_style, position, match_width)
+                        } else {
+                            popup_style.to_string()
+                        }}
+                        dir={direction.as_str()}
+                        data-state={open_state(*open && !disabled)}
+                        data-selectrs-theme={theme.filter(|_| portal)}
+                        data-placement={floating.then(|| position.map_or(placement, |(position, _)| position.placement).as_str())}
+                        onmousedown={|event: MouseEvent| event.prevent_default()}
+                    >
+                        if let Some(results) = &results {
+                            { for results.iter().map(|result| {
+                                let value = result.props.value.to_string();
+                                let mut props = detached_option(
+                                    &result.props,
+                                    option_id(&list_id, &value),
+                                    *highlighted == Some(Highlight::Value(value.clone())),
+                                );
+                                if let Some(found) = &result.label_match {
+                                    props.label = Children::new(vec![highlight_matches(&result.label, found)]);
+                                }
+                                html! { <DetachedOption key={value} ..props /> }
+                            }) }
+                        } else {
+                            { for sections }
+                        }
+                        { options }
+                        if entries.is_empty() || results.as_ref().is_some_and(Vec::is_empty) {
+                            <li class="selectrs-empty" role="presentation">
+                                { render_slot(&empty_state).unwrap_or_else(|| html! { locale.no_results }) }
+                            </li>
+                        }
+                    </ul>
+                }) }
+                // Visually hidden mirror of the selection, so the custom select takes part in native
+                // form submission and validation.
+                <select
+                    ref={select_ref}
+                    name={name}
+                    multiple={multiple}
+                    disabled={disabled}
+                    required={required}
+                    form={form}
+                    tabindex="-1"
+                    aria-hidden="true"
+                    style={VISUALLY_HIDDEN}
+                    oninvalid={oninvalid}
+                >
+                    { for report(&selected).into_iter().map(|value| html! {
+                        <option key={value.clone()} value={value.clone()} selected=true>{ value }</option>
+                    }) }
+                </select>
+            } else {
+                { prefix }
+                <select
+                    ref={select_ref}
+                    id={id}
+                    name={name}
+                    multiple={multiple}
+                    class={classes!("selectrs-trigger", select_class)}
+                    style={select_style}
+                    aria-invalid={error.is_some().then_some("true")}
+                    aria-describedby={error.is_some().then(|| error_id(&list_id))}
+                    oninvalid={oninvalid}
+                    onchange={onselectchange}
+                    disabled={disabled}
+                    size={size.to_string()}
+                    required={required}
+                    form={form}
+                    autocomplete={autocomplete}
+                    autofocus={autofocus}
+                    data-disabled={data_flag(disabled)}
+                    data-placeholder={data_flag(selected.is_empty())}
+                >
+                    { if ((!placeholder.is_empty() || render_placeholder.is_some()) && selected.is_empty()) || disabled {
+                        html! { <option value="" disabled=true selected=true data-placeholder="">{ placeholder_content }</option> }
+                    } else {
+                        html! {}
+                    } }
+                    { options }
+                </select>
+                { suffix }
+            }
+            if let Some(error) = error {
+                <div id={error_id(&list_id)} role="alert" class={classes!("selectrs-error", error_class)} style={error_style} data-state="invalid">
+                    { error }
+                </div>
+            }
+        </div>
+    }
+}
+
+/// Checks that `count` selected values respect the `min` and `max` limits, returning the localized error
+/// message otherwise.
+fn check_limits(
+    count: usize,
+    min: std::option::Option<usize>,
+    max: std::option::Option<usize>,
+    locale: &SelectLocale,
+) -> Result<(), String> {
+    match (min, max) {
+        (Some(min), _) if count < min => Err(SelectLocale::fill(locale.too_few, min)),
+        (_, Some(max)) if count > max => Err(SelectLocale::fill(locale.too_many, max)),
+        _ => Ok(()),
+    }
+}
+
+/// Counter used to generate unique listbox ids for custom popups without an explicit `id`.
+static NEXT_LIST_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the DOM id of the listbox of a `Select` or `Cascader`, derived from the `id` of its trigger or
+/// generated once if it has none.
+#[hook]
+fn use_list_id(id: &str) -> UseStateHandle<String> {
+    let id = id.to_string();
+    use_state(move || {
+        if id.is_empty() {
+            format!("selectrs-{}", NEXT_LIST_ID.fetch_add(1, Ordering::Relaxed))
+        } else {
+            format!("{}-listbox", id)
+        }
+    })
+}
+
+/// Returns the callback opening or closing the popup of a `Select` or `Cascader` when its trigger is clicked.
+fn toggle_popup(open: &UseStateHandle<bool>, disabled: bool) -> Callback<MouseEvent> {
+    let open = open.clone();
+    Callback::from(move |_: MouseEvent| {
+        if !disabled {
+            open.set(!*open);
+        }
+    })
+}
+
+/// Returns the index of the item highlighted after pressing `key` in a list of `len` items whose item
+/// `current` is highlighted, or `None` if the key does not move the highlight or the list is empty.
+fn step_highlight(
+    key: &str,
+    current: std::option::Option<usize>,
+    len: usize,
+) -> std::option::Option<usize> {
+    let last = len.checked_sub(1)?;
+    match (key, current) {
+        ("ArrowDown", Some(index)) => Some((index + 1).min(last)),
+        ("ArrowUp", Some(index)) => Some(index.saturating_sub(1)),
+        ("ArrowUp", None) | ("End", _) => Some(last),
+        ("ArrowDown" | "Home", _) => Some(0),
+        _ => None,
+    }
+}
+
+/// Closes the popup of a `Select` or `Cascader` on `Escape`, moving the focus back to the trigger behind
+/// `trigger`, and on `Tab`, which moves the focus on.
+fn close_popup(event: &KeyboardEvent, open: &UseStateHandle<bool>, trigger: &NodeRef) {
+    match event.key().as_str() {
+        "Escape" if **open => {
+            event.prevent_default();
+            focus_trigger(trigger);
+            open.set(false);
+        }
+        "Tab" => open.set(false),
+        _ => {}
+    }
+}
+
+/// Returns whether a `focusin` or `focusout` event moves the focus across the boundary of the element behind
+/// `wrapper`, i.e. whether the element gaining or losing the focus on the other side lies outside of it.
+fn focus_crosses(event: &FocusEvent, wrapper: &NodeRef) -> bool {
+    !event
+        .related_target()
+        .and_then(|target| target.dyn_into::<Node>().ok())
+        .zip(wrapper.get())
+        .map(|(target, wrapper)| wrapper.contains(Some(&target)))
+        .unwrap_or(false)
+}
+
+/// Inline style hiding an element visually while keeping it focusable for native validation messages.
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; border: 0; opacity: 0; overflow: hidden; pointer-events: none;";
+
+/// Returns the locale of a component: `locale` if given, or the one provided by an enclosing
+/// `SelectLocaleProvider`, or the English defaults.
+#[hook]
+fn use_locale(locale: std::option::Option<SelectLocale>) -> SelectLocale {
+    let provided = use_context::<SelectLocale>();
+    locale.or(provided).unwrap_or_default()
+}
+
+/// Returns the writing direction of a component: `dir` if given, or the one provided by an enclosing
+/// `ContextProvider<Direction>`, or `Direction::Ltr`.
+#[hook]
+fn use_direction(dir: std::option::Option<Direction>) -> Direction {
+    let provided = use_context::<Direction>();
+    dir.or(provided).unwrap_or_default()
+}
+
+/// Maps the left and right arrow keys to their logical meaning, so that `"ArrowRight"` always moves towards
+/// the end and `"ArrowLeft"` towards the start, whatever the writing direction. Other keys are returned as is.
+fn logical_arrow(key: String, direction: Direction) -> String {
+    match (key.as_str(), direction) {
+        ("ArrowLeft", Direction::Rtl) => "ArrowRight".to_string(),
+        ("ArrowRight", Direction::Rtl) => "ArrowLeft".to_string(),
+        _ => key,
+    }
+}
+
+/// Returns the chips of the container behind `chips` and the index of the chip containing the target of
+/// `event`, if any.
+fn focused_chip(event: &Event, chips: &NodeRef) -> std::option::Option<(HtmlCollection, u32)> {
+    let target = event.target()?.dyn_into::<Node>().ok()?;
+    let chips = chips.cast::<Element>()?.children();
+    let index = (0..chips.length()).find(|&index| {
+        chips
+            .item(index)
+            .is_some_and(|chip| chip.contains(Some(&target)))
+    })?;
+    Some((chips, index))
+}
+
+/// Focuses the first focusable element of `chip`, such as its close button, or the chip itself.
+fn focus_chip(chip: &Element) {
+    let element = chip
+        .query_selector("button, [href], input, [tabindex]")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| chip.clone());
+    if let Ok(element) = element.dyn_into::<HtmlElement>() {
+        let _ = element.focus();
+    }
+}
+
+/// Returns the value of a boolean `data-*` attribute: present and empty if `state` holds, absent otherwise.
+fn data_flag(state: bool) -> std::option::Option<&'static str> {
+    state.then_some("")
+}
+
+/// Returns the `data-state` value of a trigger or popup.
+fn open_state(open: bool) -> &'static str {
+    if open {
+        "open"
+    } else {
+        "closed"
+    }
+}
+
+/// Returns the `data-state` value of an option.
+fn selection_state(selected: bool) -> &'static str {
+    if selected {
+        "selected"
+    } else {
+        "unselected"
+    }
+}
+
+/// Returns the `data-selectrs-theme` value provided by an enclosing `SelectThemeProvider`, if any.
+#[cfg(feature = "theme")]
+#[hook]
+fn use_theme() -> std::option::Option<&'static str> {
+    use_context::<SelectTheme>().map(SelectTheme::as_str)
+}
+
+/// Returns the `data-selectrs-theme` value provided by an enclosing `SelectThemeProvider`, if any.
+#[cfg(not(feature = "theme"))]
+#[hook]
+fn use_theme() -> std::option::Option<&'static str> {
+    None
+}
+
+/// Options of the [`use_floating`] hook.
+#[derive(Clone, Copy, PartialEq)]
+struct FloatingOptions {
+    /// Whether the popup is open and should be positioned.
+    active: bool,
+    placement: Placement,
+    direction: Direction,
+    offset: f64,
+    match_width: bool,
+    /// Changes whenever the trigger may have been resized, e.g. when chips are added.
+    revision: usize,
+}
+
+/// Positions the popup behind `popup` next to the trigger behind `trigger` while `options.active` is set,
+/// updating the position whenever the page is scrolled or resized.
+///
+/// Returns the computed position together with the width of the trigger.
+#[hook]
+fn use_floating(
+    trigger: NodeRef,
+    popup: NodeRef,
+    options: FloatingOptions,
+) -> std::option::Option<(PopupPosition, f64)> {
+    let position = use_state(|| None::<(PopupPosition, f64)>);
+
+    {
+        let position = position.clone();
+        use_effect_with(options, move |options| {
+            let options = *options;
+            let update = Rc::new({
+                let position = position.clone();
+                move || {
+                    let (Some(trigger), Some(popup)) =
+                        (trigger.cast::<Element>(), popup.cast::<Element>())
+                    else {
+                        return;
+                    };
+                    let viewport = trigger
+                        .owner_document()
+                        .and_then(|document| document.document_element())
+                        .map(|root| {
+                            Rect::new(
+                                0.0,
+                                0.0,
+                                root.client_width().into(),
+                                root.client_height().into(),
+                            )
+                        })
+                        .unwrap_or_default();
+                    let trigger = client_rect(&trigger);
+                    let mut popup = client_rect(&popup);
+                    if options.match_width {
+                        popup.width = trigger.width;
+                    }
+                    position.set(Some((
+                        compute_position(
+                            trigger,
+                            popup,
+                            viewport,
+                            options.placement,
+                            options.direction,
+                            options.offset,
+                        ),
+                        trigger.width,
+                    )));
+                }
+            });
+
+            let listeners = match web_sys::window() {
+                Some(window) if options.active => {
+                    update();
+                    // Scroll events do not bubble, so they are captured to catch scrolling ancestors too.
+                    ["scroll", "resize"]
+                        .into_iter()
+                        .map(|event| {
+                            let update = update.clone();
+                            EventListener::new_with_options(
+                                &window,
+                                event,
+                                EventListenerOptions::run_in_capture_phase(),
+                                move |_| update(),
+                            )
+                        })
+                        .collect()
+                }
+                _ => {
+                    position.set(None);
+                    Vec::new()
+                }
+            };
+            move || drop(listeners)
+        });
+    }
+
+    *position
+}
+
+/// Returns the bounding rectangle of `element` in viewport coordinates.
+fn client_rect(element: &Element) -> Rect {
+    let rect = element.get_bounding_client_rect();
+    Rect::new(rect.x(), rect.y(), rect.width(), rect.height())
+}
+
+/// Returns the inline style of a floating popup at `position`, hidden until the position is known.
+fn floating_style(
+    style: &str,
+    position: std::option::Option<(PopupPosition, f64)>,
+    match_width: bool,
+) -> String {
+    match position {
+        Some((position, width)) => format!(
+            "{} position: fixed; top: {}px; left: {}px; max-height: {}px; overflow-y: auto;{}",
+            style,
+            position.y,
+            position.x,
+            position.max_height,
+            if match_width {
+                format!(" width: {}px; box-sizing: border-box;", width)
+            } else {
+                String::new()
+            },
+        ),
+        None => format!(
+            "{} position: fixed; top: 0; left: 0; visibility: hidden;",
+            style
+        ),
+    }
+}
+
+/// Renders `popup` into `host`, or the document body if no host is given, when `portal` is set.
+fn floating_portal(portal: bool, host: std::option::Option<Element>, popup: Html) -> Html {
+    if !portal {
+        return popup;
+    }
+    let host = host.or_else(|| {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.body())
+            .map(Element::from)
+    });
+    match host {
+        Some(host) => create_portal(popup, host),
+        None => popup,
+    }
+}
+
+/// Returns the form owning `element`: the form with the id `form` if given, or its nearest ancestor form.
+fn owning_form(element: &Element, form: &str) -> std::option::Option<HtmlFormElement> {
+    let form = if form.is_empty() {
+        element.closest("form").ok().flatten()
+    } else {
+        element.owner_document()?.get_element_by_id(form)
+    };
+    form?.dyn_into::<HtmlFormElement>().ok()
+}
+
+/// Calls `onreset` whenever the form owning the element behind `node` is reset.
+#[hook]
+fn use_form_reset(node: NodeRef, form: &'static str, onreset: Callback<()>) {
+    // The listener outlives renders, so it always calls the latest callback through this cell.
+    let latest = use_mut_ref(Callback::noop);
+    *latest.borrow_mut() = onreset;
+
+    use_effect_with(form, move |form| {
+        let listener = node
+            .cast::<Element>()
+            .and_then(|element| owning_form(&element, form))
+            .map(|form| EventListener::new(&form, "reset", move |_| latest.borrow().emit(())));
+        move || drop(listener)
+    });
+}
+
+/// Returns a callback that keeps its identity across renders and calls the latest `callback`, so that the
+/// context holding it only changes along with its data.
+#[hook]
+fn use_latest_callback<IN>(callback: Callback<IN>) -> Callback<IN>
+where
+    IN: 'static,
+{
+    let latest = use_mut_ref(Callback::noop);
+    *latest.borrow_mut() = callback;
+
+    (*use_memo((), move |_| {
+        Callback::from(move |input| {
+            let callback = latest.borrow().clone();
+            callback.emit(input);
+        })
+    }))
+    .clone()
+}
+
+/// Returns the DOM id of the validation error message of the listbox `list_id`.
+fn error_id(list_id: &str) -> String {
+    format!("{}-error", list_id)
+}
+
+/// Moves the focus to the trigger behind `trigger`, e.g. before the search input holding it is removed.
+fn focus_trigger(trigger: &NodeRef) {
+    if let Some(trigger) = trigger.cast::<HtmlElement>() {
+        let _ = trigger.focus();
+    }
+}
+
+/// An option matching the search query of a `Select`.
+struct SearchResult {
+    props: Rc<OptionProps>,
+    /// The text of the label of the option.
+    label: String,
+    /// The match of the query in the label, if any, as options may also match by description or keyword.
+    label_match: std::option::Option<FuzzyMatch>,
+    score: i64,
+}
+
+/// Returns the options matching `query` once normalized, the best matches first.
+fn search_options(
+    entries: &[OptionEntry],
+    query: &str,
+    normalization: Normalization,
+    locale: &SelectLocale,
+) -> Vec<SearchResult> {
+    let fuzzy_match = |query: &str, text: &str| {
+        fuzzy_match_with(query, text, |c| normalization.normalize_char(c, locale))
+    };
+    let mut results: Vec<SearchResult> = entries
+        .iter()
+        .filter_map(|entry| {
+            let data = OptionData::from(&*entry.props);
+            let label_match = fuzzy_match(query, &data.label);
+            // Matches in the description or keywords count less than matches in the label.
+            let other = std::iter::once(&data.description)
+                .chain(&data.keywords)
+                .filter_map(|text| fuzzy_match(query, text))
+                .map(|found| found.score / 2)
+                .max();
+            let score = label_match.as_ref().map(|found| found.score).max(other)?;
+            Some(SearchResult {
+                props: entry.props.clone(),
+                label: data.label,
+                label_match,
+                score,
+            })
+        })
+        .collect();
+    results.sort_by_key(|result| std::cmp::Reverse(result.score));
+    results
+}
+
+/// Renders `text` with the characters matched by `found` in bold.
+fn highlight_matches(text: &str, found: &FuzzyMatch) -> Html {
+    html! {
+        { for found.segments(text).into_iter().map(|(segment, matched)| if matched {
+            html! { <strong class="selectrs-match">{ segment.to_string() }</strong> }
+        } else {
+            html! { segment.to_string() }
+        }) }
+    }
+}
+
+/// Returns the DOM id of the tooltip describing the selection of the select with listbox `list_id`.
+fn summary_id(list_id: &str) -> String {
+    format!("{}-summary", list_id)
+}
+
+/// Returns the DOM id of the option with the given `value` inside the listbox `list_id`.
+///
+/// Ids cannot contain whitespace, so every character of the value other than an ASCII letter, digit or `-` is
+/// escaped as `_` followed by its hexadecimal code point and `_`, which keeps ids of distinct values distinct.
+fn option_id(list_id: &str, value: &str) -> String {
+    let mut id = format!("{}-option-", list_id);
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            id.push(c);
+        } else {
+            id.push_str(&format!("_{:x}_", c as u32));
+        }
+    }
+    id
+}
+
+/// Returns the DOM id of the header of the group at `path` inside the listbox `list_id`.
+fn group_id(list_id: &str, path: &[usize]) -> String {
+    let path: Vec<String> = path.iter().map(usize::to_string).collect();
+    format!("{}-group-{}", list_id, path.join("-"))
+}
+
+/// An item of the custom popup that can be highlighted with the keyboard.
+#[derive(Clone, Debug, PartialEq)]
+enum Highlight {
+    /// The option with the given value.
+    Value(String),
+    /// The header of the group at the given path.
+    Group(Vec<usize>),
+}
+
+impl Highlight {
+    /// Returns the DOM id of the item inside the listbox `list_id`.
+    fn id(&self, list_id: &str) -> String {
+        match self {
+            Self::Value(value) => option_id(list_id, value),
+            Self::Group(path) => group_id(list_id, path),
+        }
+    }
+}
+
+/// Returns the options and the headers of the groups that can be expanded and collapsed, each header before the
+/// options of its group, in display order. Disabled options and the contents of `collapsed` groups are skipped.
+fn navigable_items(
+    entries: &[OptionEntry],
+    groups: &[(Vec<usize>, Rc<GroupProps>)],
+    collapsed: &HashSet<Vec<usize>>,
+    tree: bool,
+) -> Vec<Highlight> {
+    let headers: HashSet<&[usize]> = groups
+        .iter()
+        .filter(|(_, group)| group.group && (group.collapsible || tree))
+        .map(|(path, _)| path.as_slice())
+        .collect();
+    let inside_collapsed =
+        |path: &[usize]| (1..=path.len()).any(|len| collapsed.contains(&path[..len]));
+    let mut listed = HashSet::new();
+    let mut items = Vec::new();
+    for entry in entries {
+        for len in 1..=entry.path.len() {
+            let path = &entry.path[..len];
+            if headers.contains(path) && !inside_collapsed(&path[..len - 1]) && listed.insert(path)
+            {
+                items.push(Highlight::Group(path.to_vec()));
+            }
+        }
+        if !entry.props.disabled && !inside_collapsed(&entry.path) {
+            items.push(Highlight::Value(entry.props.value.to_string()));
+        }
+    }
+    items
+}
+
+/// Returns the label of the first selected option, if any.
+fn selected_label(entries: &[OptionEntry], selected: &[String]) -> std::option::Option<Html> {
+    let value = selected.first()?;
+    entries
+        .iter()
+        .find(|entry| entry.props.value == value)
+        .map(|entry| html! { { entry.props.label.clone() } })
+}
+
+/// Returns the text of the label of the option or group with `value`, falling back to the value itself.
+fn value_text(
+    entries: &[OptionEntry],
+    groups: &[(Vec<usize>, Rc<GroupProps>)],
+    value: &str,
+) -> String {
+    if let Some(entry) = entries.iter().find(|entry| entry.props.value == value) {
+        return OptionData::from(&*entry.props).label;
+    }
+    groups
+        .iter()
+        .find(|(_, group)| group_value(group) == value)
+        .map_or(value, |(_, group)| &group.label)
+        .to_string()
+}
+
+/// Returns the label of the option or group with `value`, falling back to the value itself.
+fn value_label(
+    entries: &[OptionEntry],
+    groups: &[(Vec<usize>, Rc<GroupProps>)],
+    value: &str,
+) -> Html {
+    if let Some(entry) = entries.iter().find(|entry| entry.props.value == value) {
+        return html! { { entry.props.label.clone() } };
+    }
+    let label = groups
+        .iter()
+        .find(|(_, group)| group_value(group) == value)
+        .map_or(value, |(_, group)| &group.label)
+        .to_string();
+    html! { label }
+}
+
+/// An option registered with a `Select`.
+#[derive(Clone)]
+struct OptionEntry {
+    /// The id the option registered with.
+    id: usize,
+    /// The path of the group containing the option, as the ids of the groups from the `Select` down.
+    path: Vec<usize>,
+    props: Rc<OptionProps>,
+    /// The element rendered for the option, which gives its position in the document.
+    node: NodeRef,
+}
+
+/// Returns the registered `options` in document order. Options without a rendered element are listed last, in
+/// registration order.
+fn document_order(options: &[OptionEntry]) -> Vec<OptionEntry> {
+    let (mut rendered, unrendered): (Vec<OptionEntry>, Vec<OptionEntry>) = options
+        .iter()
+        .cloned()
+        .partition(|entry| entry.node.get().is_some());
+    rendered.sort_by(|a, b| match (a.node.get(), b.node.get()) {
+        (Some(a), Some(b)) if !a.is_same_node(Some(&b)) => {
+            if a.compare_document_position(&b) & Node::DOCUMENT_POSITION_FOLLOWING != 0 {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        }
+        _ => std::cmp::Ordering::Equal,
+    });
+    rendered.extend(unrendered);
+    rendered
+}
+
+/// Returns whether `a` and `b` list the same options, with the same props, in the same places.
+fn same_entries(a: &[OptionEntry], b: &[OptionEntry]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.id == b.id && a.path == b.path && Rc::ptr_eq(&a.props, &b.props))
+}
+
+/// Returns the `options`, given in document order, in display order: the options of every group are sorted
+/// among themselves and take the places the options of the group were rendered at, keeping their ids.
+fn display_order(options: &[OptionEntry], order: &OptionOrder) -> Vec<OptionEntry> {
+    let mut paths: Vec<&Vec<usize>> = Vec::new();
+    for entry in options {
+        if !paths.contains(&&entry.path) {
+            paths.push(&entry.path);
+        }
+    }
+
+    let mut entries = options.to_vec();
+    for path in paths {
+        let places: Vec<usize> = (0..options.len())
+            .filter(|&index| options[index].path == *path)
+            .collect();
+        let data: Vec<OptionData> = places
+            .iter()
+            .map(|&index| OptionData::from(&*options[index].props))
+            .collect();
+        for (&place, index) in places.iter().zip(order.indices(&data)) {
+            entries[place].props = options[places[index]].props.clone();
+        }
+    }
+    entries
+}
+
+/// How the children of a group are ordered for display.
+#[derive(Clone, Copy)]
+struct OptionOrder<'a> {
+    sort: &'a SortOptions,
+    selected_first: bool,
+    selected: &'a [String],
+    locale: &'a SelectLocale,
+}
+
+impl OptionOrder<'_> {
+    /// Returns the indices of the `options` of a group in display order.
+    fn indices(&self, options: &[OptionData]) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..options.len()).collect();
+        match self.sort {
+            SortOptions::None => {}
+            SortOptions::Alphabetical => {
+                indices.sort_by(|&a, &b| self.locale.compare(&options[a].label, &options[b].label))
+            }
+            SortOptions::Comparator(compare) => {
+                indices.sort_by(|&a, &b| compare.emit((options[a].clone(), options[b].clone())))
+            }
+        }
+        if self.selected_first {
+            indices.sort_by_key(|&index| !self.selected.contains(&options[index].value));
+        }
+        indices
+    }
+}
+
+/// Returns the text content of `label`, ignoring any markup.
+fn label_text(label: &Children) -> String {
+    fn walk(node: &Html, text: &mut String) {
+        match node {
+            Html::VText(node) => text.push_str(&node.text),
+            Html::VList(list) => list.iter().for_each(|node| walk(node, text)),
+            Html::VTag(tag) => {
+                if let Some(children) = tag.children() {
+                    walk(children, text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut text = String::new();
+    for node in label.iter() {
+        walk(&node, &mut text);
+    }
+    text
+}
+
+/// Returns the values of the options below the group at `path`, optionally including disabled ones.
+fn option_values(entries: &[OptionEntry], path: &[usize], include_disabled: bool) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|entry| entry.path.starts_with(path))
+        .filter(|entry| include_disabled || !entry.props.disabled)
+        .map(|entry| entry.props.value.to_string())
+        .collect()
+}
+
+/// Returns the value a group is reported with in tree mode.
+fn group_value(group: &GroupProps) -> &str {
+    if group.value.is_empty() {
+        &group.label
+    } else {
+        &group.value
+    }
+}
+
+/// Replaces the options of fully selected groups by the value of the highest such group.
+fn highest_selected(
+    entries: &[OptionEntry],
+    groups: &[(Vec<usize>, Rc<GroupProps>)],
+    selected: &[String],
+) -> Vec<String> {
+    let full: Vec<(&[usize], &str)> = groups
+        .iter()
+        .filter(|(_, group)| !group_value(group).is_empty())
+        .filter(|(path, _)| {
+            let values = option_values(entries, path, false);
+            !values.is_empty() && values.iter().all(|value| selected.contains(value))
+        })
+        .map(|(path, group)| (path.as_slice(), group_value(group)))
+        .collect();
+
+    let mut values = Vec::new();
+    for entry in entries {
+        if !selected.iter().any(|v| v == entry.props.value) {
+            continue;
+        }
+        // Groups are listed parents first, so the first match is the highest ancestor.
+        let value = full
+            .iter()
+            .find(|(path, _)| entry.path.starts_with(path))
+            .map_or(&*entry.props.value, |(_, value)| value);
+        if !values.iter().any(|v| v == value) {
+            values.push(value.to_string());
+        }
+    }
+    values
+}
+
+/// Controls which values a tree `Select` reports through `onchange`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TreeOutput {
+    /// Report the value of every selected option.
+    #[default]
+    Leaves,
+    /// Report the value of the highest fully selected group in place of its options.
+    Ancestors,
+}
+
+/// Controls how the options of each group of a `Select` are sorted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SortOptions {
+    /// Keep the options in the order of the children.
+    #[default]
+    None,
+    /// Sort the options by label, according to the language of the locale.
+    Alphabetical,
+    /// Sort the options with a custom comparator.
+    Comparator(Callback<(OptionData, OptionData), std::cmp::Ordering>),
+}
+
+/// Controls the order of the selected values of a `Select`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChipOrder {
+    /// Keep the values in the order in which they were selected.
+    #[default]
+    Selection,
+    /// Keep the values in the order of the options, as sorted by `sort_options`.
+    Options,
+}
+
+/// The data of an `Option`, as passed to a custom comparator and matched when searching.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionData {
+    /// The value of the option.
+    pub value: String,
+    /// The text of the label of the option, or its value if it has no label.
+    pub label: String,
+    /// Whether the option is disabled.
+    pub disabled: bool,
+    /// The description of the option.
+    pub description: String,
+    /// The additional terms matched when searching the option.
+    pub keywords: Vec<String>,
+}
+
+impl OptionData {
+    /// Returns whether the label, the description or one of the keywords of the option contains `query`,
+    /// after both are normalized with `Normalization::default()`, ignoring case according to the language of
+    /// `locale` and accents.
+    ///
+    /// # Example
+    /// ```rust
+    /// use selectrs::yew::{OptionData, SelectLocale};
+    ///
+    /// let option = OptionData {
+    ///     value: "nyc".to_string(),
+    ///     label: "New York".to_string(),
+    ///     disabled: false,
+    ///     description: "The Big Apple".to_string(),
+    ///     keywords: vec!["NYC".to_string(), "Manhattan".to_string()],
+    /// };
+    /// let locale = SelectLocale::default();
+    /// assert!(option.matches("york", &locale));
+    /// assert!(option.matches("apple", &locale));
+    /// assert!(option.matches("nyc", &locale));
+    /// assert!(option.matches("mánhattan", &locale));
+    /// assert!(!option.matches("boston", &locale));
+    /// ```
+    pub fn matches(&self, query: &str, locale: &SelectLocale) -> bool {
+        let normalize = |text: &str| Normalization::default().normalize(text, locale);
+        let query = normalize(query);
+        std::iter::once(&self.label)
+            .chain([&self.description])
+            .chain(&self.keywords)
+            .any(|text| normalize(text).contains(&query))
+    }
+}
+
+impl From<&OptionProps> for OptionData {
+    fn from(props: &OptionProps) -> Self {
+        let label = label_text(&props.label);
+        Self {
+            value: props.value.to_string(),
+            label: if label.is_empty() {
+                props.value.to_string()
+            } else {
+                label
+            },
+            disabled: props.disabled,
+            description: props.description.to_string(),
+            keywords: props.keywords.clone(),
+        }
+    }
+}
+
+/// Controls how the selected values of a `Select` are displayed in `multiple` mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// Display each value as a removable chip.
+    #[default]
+    Chips,
+    /// Display the number of selected values, e.g. "3 selected".
+    Count,
+    /// Display the labels of the selected values, separated by commas.
+    CommaList,
+}
+
+/// Configures the history of recently chosen values of a `Select`.
+///
+/// # Example
+/// ```rust
+/// use std::rc::Rc;
+/// use selectrs::yew::{MemoryStorage, RecentConfig, SelectStorage};
+///
+/// let storage = MemoryStorage::default();
+/// let recent = RecentConfig { storage: Rc::new(storage.clone()), ..RecentConfig::new("projects", 2) };
+/// let history = recent.record(&recent.load(), &["website".to_string()]);
+/// let history = recent.record(&history, &["mobile".to_string()]);
+/// assert_eq!(history, ["mobile", "website"]);
+/// assert_eq!(storage.get_list("projects"), ["mobile", "website"]);
+/// ```
+#[derive(Clone)]
+pub struct RecentConfig {
+    /// The maximum number of recent values, `0` disabling the history.
+    pub size: usize,
+    /// The key under which the history is stored.
+    pub key: &'static str,
+    /// The storage persisting the history.
+    pub storage: Rc<dyn SelectStorage>,
+}
+
+impl RecentConfig {
+    /// Creates a history of `size` values, stored under `key` in `localStorage`.
+    pub fn new(key: &'static str, size: usize) -> Self {
+        Self {
+            size,
+            key,
+            storage: Rc::new(LocalStorage),
+        }
+    }
+
+    /// Returns the stored history, most recent first.
+    pub fn load(&self) -> Vec<String> {
+        if self.size == 0 {
+            return Vec::new();
+        }
+        self.storage.get_list(self.key)
+    }
+
+    /// Moves the `chosen` values to the front of `history`, stores the result and returns it.
+    pub fn record(&self, history: &[String], chosen: &[String]) -> Vec<String> {
+        let history = push_recent(history, chosen, self.size);
+        self.storage.set_list(self.key, &history);
+        history
+    }
+}
+
+impl Default for RecentConfig {
+    fn default() -> Self {
+        Self::new("", 0)
+    }
+}
+
+impl PartialEq for RecentConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+            && self.key == other.key
+            && Rc::ptr_eq(&self.storage, &other.storage)
+    }
+}
+
+/// Binds the selection of a `Select` to a query parameter of the URL, with the `router` feature.
+///
+/// # Example
+/// ```rust
+/// use yew::prelude::*;
+/// use yew_router::prelude::*;
+/// use selectrs::yew::{HistoryMode, QueryEncoding, QuerySync, Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     // Reads and writes `?tags=rust,yew`, replacing the history entry on every change.
+///     let query_sync = QuerySync {
+///         encoding: QueryEncoding::Comma,
+///         history: HistoryMode::Replace,
+///         ..QuerySync::new("tags")
+///     };
+///     html! {
+///         <BrowserRouter>
+///             <Select multiple=true query_sync={query_sync} placeholder="Filter by tag...">
+///                 <Group>
+///                     <Option value="rust" label="Rust" />
+///                     <Option value="yew" label="Yew" />
+///                 </Group>
+///             </Select>
+///         </BrowserRouter>
+///     }
+/// }
+/// ```
+#[cfg(feature = "router")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuerySync {
+    /// The name of the query parameter, an empty name disabling the synchronization.
+    pub key: &'static str,
+    /// How the values are encoded in the parameter.
+    pub encoding: QueryEncoding,
+    /// Whether changes push a new history entry or replace the current one.
+    pub history: HistoryMode,
+}
+
+#[cfg(feature = "router")]
+impl QuerySync {
+    /// Binds the selection to the query parameter `key`, with repeated keys and a new history entry per change.
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the values of the parameter in `location`, or `None` if it is missing or disabled.
+    fn read(&self, location: &yew_router::history::Location) -> std::option::Option<Vec<String>> {
+        if self.key.is_empty() {
+            return None;
+        }
+        read_query(location.query_str(), self.key, self.encoding)
+    }
+
+    /// Navigates to `location` with the parameter set to `values`, unless it already has these values.
+    fn write(
+        &self,
+        navigator: &yew_router::prelude::Navigator,
+        location: &yew_router::history::Location,
+        values: &[String],
+    ) {
+        if self.key.is_empty() {
+            return;
+        }
+        let query = write_query(location.query_str(), self.key, values, self.encoding);
+        if query == location.query_str() {
+            return;
+        }
+        // The navigator prefixes routes with its basename, which the location path already includes.
+        let path = location.path();
+        let path = match navigator
+            .basename()
+            .and_then(|base| path.strip_prefix(base))
+        {
+            Some("") => "/",
+            Some(stripped) if stripped.starts_with('/') => stripped,
+            _ => path,
+        };
+        let route = yew_router::AnyRoute::new(format!("{path}{query}{}", location.hash()));
+        match self.history {
+            HistoryMode::Push => navigator.push(&route),
+            HistoryMode::Replace => navigator.replace(&route),
+        }
+    }
+}
+
+/// How a `Select` bound to a query parameter updates the browser history.
+#[cfg(feature = "router")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HistoryMode {
+    /// Push a new history entry on every change, so that the back button restores the previous selection.
+    #[default]
+    Push,
+    /// Replace the current history entry, keeping the history short.
+    Replace,
+}
+
+/// Where a `Select` persists its selection.
+#[derive(Clone, Default)]
+pub enum Persist {
+    /// Do not persist the selection.
+    #[default]
+    None,
+    /// Persist the selection in the `localStorage` of the browser, across sessions.
+    Local,
+    /// Persist the selection in the `sessionStorage` of the browser, for as long as the tab is open.
+    Session,
+    /// Persist the selection in a custom store.
+    Store(Rc<dyn SelectionStore>),
+}
+
+impl Persist {
+    fn load(&self, key: &str) -> std::option::Option<Vec<String>> {
+        match self {
+            Persist::None => None,
+            Persist::Local => LocalStorage.load(key),
+            Persist::Session => SessionStorage.load(key),
+            Persist::Store(store) => store.load(key),
+        }
+    }
+
+    fn save(&self, key: &str, values: &[String]) {
+        match self {
+            Persist::None => {}
+            Persist::Local => LocalStorage.save(key, values),
+            Persist::Session => SessionStorage.save(key, values),
+            Persist::Store(store) => store.save(key, values),
+        }
+    }
+}
+
+impl PartialEq for Persist {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Persist::Store(a), Persist::Store(b)) => Rc::ptr_eq(a, b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// Returns the storage key of the persisted selection of a `Select`, if it is persisted and has a `name` or an
+/// `id`.
+fn persist_key(persist: &Persist, name: &str, id: &str) -> std::option::Option<String> {
+    if *persist == Persist::None {
+        return None;
+    }
+    [name, id]
+        .into_iter()
+        .find(|key| !key.is_empty())
+        .map(|key| format!("selectrs:{key}"))
+}
+
+/// Keeps the `values` with a matching option in `entries`.
+fn valid_values(entries: &[OptionEntry], values: Vec<String>) -> Vec<String> {
+    let options: HashSet<&str> = entries.iter().map(|entry| &*entry.props.value).collect();
+    values
+        .into_iter()
+        .filter(|value| options.contains(value.as_str()))
+        .collect()
+}
+
+/// A storage backed by the `localStorage` of the browser, persisting values across sessions.
+///
+/// Values cannot be read or written when storage is unavailable, e.g. in private browsing modes that disable
+/// it or outside of a browser, in which case the storage behaves as if it were empty.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LocalStorage;
+
+impl SelectStorage for LocalStorage {
+    fn get(&self, key: &str) -> std::option::Option<String> {
+        web_sys::window()?
+            .local_storage()
+            .ok()
+            .flatten()?
+            .get_item(key)
+            .ok()
+            .flatten()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        if let Some(storage) =
+            web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+        {
+            let _ = storage.set_item(key, value);
+        }
+    }
+}
+
+/// A storage backed by the `sessionStorage` of the browser, persisting values for as long as the tab is open.
+///
+/// Like [`LocalStorage`], it behaves as if it were empty when storage is unavailable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SessionStorage;
+
+impl SelectStorage for SessionStorage {
+    fn get(&self, key: &str) -> std::option::Option<String> {
+        web_sys::window()?
+            .session_storage()
+            .ok()
+            .flatten()?
+            .get_item(key)
+            .ok()
+            .flatten()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        if let Some(storage) =
+            web_sys::window().and_then(|window| window.session_storage().ok().flatten())
+        {
+            let _ = storage.set_item(key, value);
+        }
+    }
+}
+
+/// Describes a change of the selection of a `Select`, as passed to its `onchange_detail` callback.
+///
+/// # Example
+/// ```rust
+/// use selectrs::yew::{ChangeSource, SelectionChange};
+///
+/// let old = ["apple".to_string(), "banana".to_string()];
+/// let new = ["banana".to_string(), "cherry".to_string()];
+/// let change = SelectionChange::between(&old, &new, ChangeSource::OptionClick);
+/// assert_eq!(change.added, ["cherry"]);
+/// assert_eq!(change.removed, ["apple"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectionChange {
+    /// The values added to the selection, in selection order.
+    pub added: Vec<String>,
+    /// The values removed from the selection, in their previous order.
+    pub removed: Vec<String>,
+    /// What caused the change.
+    pub source: ChangeSource,
+}
+
+impl SelectionChange {
+    /// Returns the change from the `old` selection to the `new` one.
+    pub fn between(old: &[String], new: &[String], source: ChangeSource) -> Self {
+        Self {
+            added: new
+                .iter()
+                .filter(|value| !old.contains(value))
+                .cloned()
+                .collect(),
+            removed: old
+                .iter()
+                .filter(|value| !new.contains(value))
+                .cloned()
+                .collect(),
+            source,
+        }
+    }
+}
+
+/// A handle controlling a `Select` from the outside, passed through its `handle` property.
+///
+/// Clones control the same component. Methods called while no component is mounted with the handle do
+/// nothing.
+///
+/// # Example
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{use_select_handle, Select, Option, Group};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let handle = use_select_handle();
+///     let clear = {
+///         let handle = handle.clone();
+///         Callback::from(move |_| handle.clear())
+///     };
+///     let pick_all = {
+///         let handle = handle.clone();
+///         Callback::from(move |_| handle.set_value(vec!["apple".into(), "banana".into()]))
+///     };
+///     html! {
+///         <>
+///             <button onclick={clear}>{ "Clear" }</button>
+///             <button onclick={pick_all}>{ "All fruits" }</button>
+///             <Select custom=true multiple=true handle={handle.clone()}>
+///                 <Group>
+///                     <Option value="apple" label="Apple" />
+///                     <Option value="banana" label="Banana" />
+///                 </Group>
+///             </Select>
+///         </>
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct SelectHandle {
+    target: Rc<RefCell<std::option::Option<Callback<SelectCommand>>>>,
+}
+
+impl SelectHandle {
+    /// Focuses the component and opens its custom popup.
+    pub fn open(&self) {
+        self.send(SelectCommand::Open);
+    }
+
+    /// Closes the custom popup.
+    pub fn close(&self) {
+        self.send(SelectCommand::Close);
+    }
+
+    /// Moves the focus to the trigger of the custom popup or to the native `select`.
+    pub fn focus(&self) {
+        self.send(SelectCommand::Focus);
+    }
+
+    /// Deselects every value.
+    pub fn clear(&self) {
+        self.send(SelectCommand::Clear);
+    }
+
+    /// Replaces the selection with `values`.
+    pub fn set_value(&self, values: Vec<String>) {
+        self.send(SelectCommand::SetValue(values));
+    }
+
+    /// Selects `value`, replacing the selection in single mode.
+    pub fn select(&self, value: impl Into<String>) {
+        self.send(SelectCommand::Select(value.into()));
+    }
+
+    /// Deselects `value`.
+    pub fn deselect(&self, value: impl Into<String>) {
+        self.send(SelectCommand::Deselect(value.into()));
+    }
+
+    fn send(&self, command: SelectCommand) {
+        let target = self.target.borrow().clone();
+        if let Some(target) = target {
+            target.emit(command);
+        }
+    }
+
+    /// Routes the commands to `target`, the latest command callback of the component holding the handle.
+    fn attach(&self, target: Callback<SelectCommand>) {
+        *self.target.borrow_mut() = Some(target);
+    }
+
+    fn detach(&self) {
+        *self.target.borrow_mut() = None;
+    }
+}
+
+impl PartialEq for SelectHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.target, &other.target)
+    }
+}
+
+/// A command sent to a `Select` through its `SelectHandle`.
+enum SelectCommand {
+    Open,
+    Close,
+    Focus,
+    Clear,
+    SetValue(Vec<String>),
+    Select(String),
+    Deselect(String),
+}
+
+/// Returns a `SelectHandle` that stays the same across renders, to pass to the `handle` property of a `Select`.
+#[hook]
+pub fn use_select_handle() -> SelectHandle {
+    (*use_state(SelectHandle::default)).clone()
+}
+
+/// What caused a change of the selection of a `Select`.
+///
+/// More sources may be added in future releases, so matches on it need a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChangeSource {
+    /// An option or, in tree mode, a group header was clicked, or an option was picked in a native select.
+    OptionClick,
+    /// The close button of a chip was used.
+    ChipRemove,
+    /// The selection was reset with the owning form.
+    Clear,
+    /// An option was picked with the keyboard in the custom popup.
+    Keyboard,
+    /// The selection was changed by the application, e.g. restored from storage or the URL, or changed through a
+    /// `SelectHandle`.
+    Programmatic,
+    /// A chip was moved by dragging it or with the keyboard. No value is added or removed; the new order is
+    /// reported through `onchange` and `onreorder`.
+    Reorder,
+}
+
+/// Describes a chip moved by the user, as passed to the `onreorder` callback of a `Select`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChipReorder {
+    /// The value of the moved chip.
+    pub value: String,
+    /// The previous position of the chip.
+    pub from: usize,
+    /// The new position of the chip.
+    pub to: usize,
+}
+
+/// Describes a chip passed to the `render_chip` slot of a `Select`.
+#[derive(Clone, PartialEq)]
+pub struct ChipContext {
+    /// The selected value shown by the chip.
+    pub value: String,
+    /// The label of the option or group with this value, or the value itself if none is found.
+    pub label: Html,
+    /// The position of the chip among all chips.
+    pub index: usize,
+    /// Whether the select is disabled.
+    pub disabled: bool,
+    /// Deselects the value of the chip.
+    pub remove: Callback<()>,
+}
+
+/// Describes the state of a `Select`, passed to its render slots.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotContext {
+    /// Whether the popup is open.
+    pub open: bool,
+    /// Whether the select is disabled.
+    pub disabled: bool,
+    /// Whether the select allows multiple selections.
+    pub multiple: bool,
+    /// The `placeholder` text of the select.
+    pub placeholder: AttrValue,
+    /// The selected values, as reported through `onchange`.
+    pub selected: Vec<String>,
+}
+
+/// The state a `Select` shares with the groups and options rendered inside it.
+///
+/// A `Select` provides its context to all of its descendants, and every `Group` provides it again to its own
+/// descendants. `Option` and `Group` components read the selection from it and register with their `Select`
+/// through it, so they may be wrapped in other components, mixed with other markup or rendered conditionally.
+/// Read it with [`use_select_context`], e.g. to style a wrapper after the state of its option.
+#[derive(Clone)]
+pub struct SelectContext {
+    /// The selected values, in selection order.
+    pub selected: Rc<Vec<String>>,
+    /// The value of the option highlighted with the keyboard in the custom popup, if any.
+    pub highlighted: std::option::Option<String>,
+    /// The path of the group whose header is highlighted with the keyboard, if any.
+    highlighted_group: std::option::Option<Vec<usize>>,
+    /// Whether several options can be selected.
+    pub multiple: bool,
+    /// Whether the options are rendered in a custom popup list instead of a native `select`.
+    pub custom: bool,
+    /// Whether the groups and options are rendered as a tree.
+    pub tree: bool,
+    /// The nesting level of the surrounding groups, starting at `0` directly inside the `Select`.
+    pub depth: usize,
+    /// The ids of the surrounding groups, from the `Select` down.
+    path: Vec<usize>,
+    /// Whether the options are rendered hidden, e.g. while the search results are listed in their place.
+    hidden: bool,
+    indent: &'static str,
+    list_id: String,
+    /// Selects or deselects the option with the given value.
+    select: Callback<String>,
+    /// Whether clicking an option selects it.
+    clickable: bool,
+    /// The registered options in display order.
+    entries: Rc<Vec<OptionEntry>>,
+    /// The props of the option displayed in the place of every registered option, by id.
+    shown: Rc<HashMap<usize, Rc<OptionProps>>>,
+    /// The paths of the groups expanded or collapsed by the user since their initial state.
+    toggled: Rc<HashSet<Vec<usize>>>,
+    toggle_group: Callback<Vec<usize>>,
+    select_group: Callback<(Vec<usize>, ChangeSource)>,
+    registry: Rc<RefCell<Registry>>,
+    /// Renders the `Select` again once its registry has changed.
+    refresh: Callback<()>,
+}
+
+impl SelectContext {
+    /// Returns whether the option with `value` is selected.
+    pub fn is_selected(&self, value: &str) -> bool {
+        self.selected.iter().any(|v| v == value)
+    }
+
+    /// Returns whether the option with `value` is highlighted with the keyboard.
+    pub fn is_highlighted(&self, value: &str) -> bool {
+        self.highlighted.as_deref() == Some(value)
+    }
+
+    /// Selects or deselects the option with `value` as if it was clicked.
+    ///
+    /// In single mode, the value replaces the selection and the popup closes. The change is reported with the
+    /// `OptionClick` source.
+    pub fn toggle(&self, value: impl Into<String>) {
+        self.select.emit(value.into());
+    }
+
+    /// Returns the context provided to the children of the group `id`.
+    fn nested(&self, id: usize, group: bool) -> Self {
+        let mut path = self.path.clone();
+        path.push(id);
+        Self {
+            depth: if group { self.depth + 1 } else { self.depth },
+            path,
+            ..self.clone()
+        }
+    }
+
+    /// Registers or updates the option `id` rendered into `node`.
+    fn register_option(&self, id: usize, props: Rc<OptionProps>, node: NodeRef) {
+        let entry = OptionEntry {
+            id,
+            path: self.path.clone(),
+            props,
+            node,
+        };
+        let mut registry = self.registry.borrow_mut();
+        match registry.options.iter_mut().find(|entry| entry.id == id) {
+            Some(registered) => *registered = entry,
+            None => registry.options.push(entry),
+        }
+        drop(registry);
+        self.changed();
+    }
+
+    /// Registers or updates the group `id`.
+    fn register_group(&self, id: usize, props: Rc<GroupProps>) {
+        let mut path = self.path.clone();
+        path.push(id);
+        let mut registry = self.registry.borrow_mut();
+        registry.groups.retain(|(path, _)| path.last() != Some(&id));
+        registry.groups.push((path, props));
+        drop(registry);
+        self.changed();
+    }
+
+    /// Removes the option or group `id` once it is no longer rendered.
+    fn unregister(&self, id: usize) {
+        let mut registry = self.registry.borrow_mut();
+        registry.options.retain(|entry| entry.id != id);
+        registry.groups.retain(|(path, _)| path.last() != Some(&id));
+        drop(registry);
+        self.changed();
+    }
+
+    /// Renders the `Select` again after a registration, once for all the registrations made before it renders.
+    fn changed(&self) {
+        let pending = std::mem::replace(&mut self.registry.borrow_mut().pending, true);
+        if !pending {
+            self.refresh.emit(());
+        }
+    }
+}
+
+impl PartialEq for SelectContext {
+    fn eq(&self, other: &Self) -> bool {
+        // The options and the registry are compared by identity, since comparing every option is costly.
+        self.selected == other.selected
+            && self.highlighted == other.highlighted
+            && self.highlighted_group == other.highlighted_group
+            && self.multiple == other.multiple
+            && self.custom == other.custom
+            && self.tree == other.tree
+            && self.depth == other.depth
+            && self.path == other.path
+            && self.hidden == other.hidden
+            && self.indent == other.indent
+            && self.list_id == other.list_id
+            && self.select == other.select
+            && self.clickable == other.clickable
+            && Rc::ptr_eq(&self.entries, &other.entries)
+            && Rc::ptr_eq(&self.shown, &other.shown)
+            && self.toggled == other.toggled
+            && self.toggle_group == other.toggle_group
+            && self.select_group == other.select_group
+            && Rc::ptr_eq(&self.registry, &other.registry)
+            && self.refresh == other.refresh
+    }
+}
+
+/// Returns the context of the surrounding `Select`, or `None` outside of a `Select`.
+///
+/// # Example
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{use_select_context, Option};
+///
+/// #[derive(Properties, PartialEq)]
+/// pub struct CheckedOptionProps {
+///     pub value: &'static str,
+///     pub label: &'static str,
+/// }
+///
+/// // An option showing a check mark as its badge while it is selected.
+/// #[function_component(CheckedOption)]
+/// pub fn checked_option(props: &CheckedOptionProps) -> Html {
+///     let selected = use_select_context().is_some_and(|context| context.is_selected(props.value));
+///
+///     html! {
+///         <Option value={props.value} label={props.label} badge={if selected { "✓" } else { "" }} />
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_select_context() -> std::option::Option<SelectContext> {
+    use_context::<SelectContext>()
+}
+
+/// The options and groups registered with a `Select`, in registration order.
+#[derive(Default)]
+struct Registry {
+    options: Vec<OptionEntry>,
+    /// The groups with their paths, as the ids of the groups from the `Select` down to the group itself.
+    groups: Vec<(Vec<usize>, Rc<GroupProps>)>,
+    /// Whether the `Select` has been asked to render again since the last registration.
+    pending: bool,
+}
+
+/// Counter used to generate the ids the options and groups register with.
+static NEXT_ITEM_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the inline style indenting an element nested `depth` levels deep.
+fn indent_style(indent: &str, depth: usize) -> String {
+    if depth == 0 {
+        String::new()
+    } else {
+        format!("padding-inline-start: calc({} * {});", depth, indent)
+    }
+}
+
+/// Joins the non-empty inline style declarations in `parts`, separated by single spaces.
+fn join_styles(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Properties for configuring the `Group` component.
+///
+/// The `Group` component allows you to group together `Option` elements.
+/// It provides customization for labels, selection behavior, and event handling. The group allows a common state and
+/// behavior across the contained options. This component supports customization of styles and classes, as well as
+/// interaction handling through the `ontoggle` callback.
+#[derive(Properties, PartialEq, Clone)]
+pub struct GroupProps {
+    /// The label for the group.
+    ///
+    /// This is the text that labels the entire group of options. This can be used to describe
+    /// the set of options the user is about to choose from, making it useful for accessibility.
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub label: AttrValue,
+
+    /// The value reported for the group in tree mode.
+    ///
+    /// When the parent `Select` uses `TreeOutput::Ancestors` and all options of this group are selected,
+    /// this value is reported in place of the option values. Defaults to the `label` if not provided.
+    #[prop_or_default]
+    pub value: AttrValue,
+
+    /// Indicates whether this is a group options.
+    ///
     /// If `group` is set to `true`, the options in this group will be considered as part of the
     /// `label` options group, where only one option can be selected at a time. If set to `false`, the
     /// group is disabled. Defaults to `false` if not provided.
     #[prop_or_default]
-    pub group: bool,
+    pub group: bool,
+
+    /// The currently selected option in the group.
+    ///
+    /// Inside a `Select`, the selection is read from its `SelectContext` instead, so this value is ignored.
+    /// It is kept so that existing code setting it still compiles. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub selected: String,
+
+    /// Callback for when the selected option changes.
+    ///
+    /// Inside a `Select`, changes are reported through its `onchange` and `onchange_detail` callbacks instead, so
+    /// this callback is never called. It is kept so that existing code setting it still compiles. Defaults to a
+    /// no-op.
+    #[prop_or_default]
+    pub onchange: Callback<String>,
+
+    /// Whether the group header expands and collapses its options when clicked or toggled with the keyboard
+    /// (custom mode only).
+    ///
+    /// Collapsible groups are only supported by the custom popup list, since a native `optgroup`
+    /// cannot hide its options. Requires `group` to be `true`. Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub collapsible: bool,
+
+    /// Whether a collapsible group starts collapsed.
+    ///
+    /// This only sets the initial state; afterwards the group is expanded and collapsed through its header.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub default_collapsed: bool,
+
+    /// Callback for when a collapsible group is expanded or collapsed.
+    ///
+    /// The callback receives `true` when the group has been collapsed and `false` when it has been expanded.
+    /// Defaults to a no-op.
+    #[prop_or_default]
+    pub ontoggle: Callback<bool>,
+
+    /// Child components of the group.
+    ///
+    /// This property allows you to pass one or more `Option` components as children of the `Group` component.
+    /// These `Option` components represent the individual selectable options within the group, and may be
+    /// wrapped in other components, mixed with other markup or rendered conditionally. Groups can be nested to
+    /// build a hierarchy, which is rendered as a tree when the parent `Select` is in tree mode, and are
+    /// flattened into their parent otherwise. Defaults to an empty list of children if not provided.
+    #[prop_or_default]
+    pub children: Children,
+
+    /// Custom CSS class for the group.
+    ///
+    /// This property allows for custom styling of the group container by specifying one or more CSS classes.
+    /// It is applied to the outer wrapper of the group, such as for styling the container element.
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub class: Classes,
+
+    /// Inline styles for the group.
+    ///
+    /// This property allows for custom inline styles to be applied directly to the group container.
+    /// It provides more granular control over the styling of the group and its elements without the need for external CSS.
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub style: &'static str,
+
+    /// Custom CSS class for the group header (custom mode only).
+    ///
+    /// This property allows for custom styling of the header that displays the group label, the selected
+    /// count and toggles collapsible groups. Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub header_class: Classes,
+
+    /// Inline styles for the group header (custom mode only).
+    ///
+    /// This property allows for custom inline styles to be applied directly to the group header.
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub header_style: &'static str,
+
+    /// Custom CSS class for the selected count in the group header (custom mode only).
+    ///
+    /// The count is only rendered when at least one option of the group is selected.
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub count_class: Classes,
+
+    /// Inline styles for the selected count in the group header (custom mode only).
+    ///
+    /// This property allows for custom inline styles to be applied directly to the selected count.
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub count_style: &'static str,
+
+    /// Custom CSS class for the expand/collapse toggle of a tree node (tree mode only).
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub toggle_class: Classes,
+
+    /// Inline styles for the expand/collapse toggle of a tree node (tree mode only).
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub toggle_style: &'static str,
+}
+
+#[function_component(Group)]
+pub fn group(props: &GroupProps) -> Html {
+    let context = use_context::<SelectContext>();
+    let id = use_state(|| NEXT_ITEM_ID.fetch_add(1, Ordering::Relaxed));
+
+    // Registers the group with the surrounding `Select`, which reports and selects it as a whole in tree mode.
+    {
+        let context = context.clone();
+        let id = *id;
+        use_effect_with(props.clone(), move |props| {
+            if let Some(context) = &context {
+                context.register_group(id, Rc::new(props.clone()));
+            }
+            move || {
+                if let Some(context) = context {
+                    context.unregister(id);
+                }
+            }
+        });
+    }
+
+    let GroupProps {
+        label,
+        group,
+        collapsible,
+        default_collapsed,
+        children,
+        class,
+        style,
+        header_class,
+        header_style,
+        count_class,
+        count_style,
+        toggle_class,
+        toggle_style,
+        ..
+    } = props.clone();
+
+    let Some(context) = context else {
+        return html! { { children } };
+    };
+    let SelectContext {
+        custom,
+        tree,
+        indent,
+        depth,
+        hidden,
+        ..
+    } = context.clone();
+    let path: Vec<usize> = context.path.iter().copied().chain([*id]).collect();
+    let items = html! {
+        <ContextProvider<SelectContext> context={context.nested(*id, group)}>
+            { children }
+        </ContextProvider<SelectContext>>
+    };
+    let style = join_styles(&[if hidden { "display: none;" } else { "" }, style]);
+
+    if custom {
+        if !group {
+            return items;
+        }
+
+        let options: Vec<&OptionEntry> = context
+            .entries
+            .iter()
+            .filter(|entry| entry.path.starts_with(&path))
+            .collect();
+        let count = options
+            .iter()
+            .filter(|option| context.is_selected(&option.props.value))
+            .count();
+        let fully_selected = options
+            .iter()
+            .filter(|option| !option.props.disabled)
+            .all(|option| context.is_selected(&option.props.value))
+            && options.iter().any(|option| !option.props.disabled);
+        let collapsible = collapsible || tree;
+        let collapsed = collapsible && default_collapsed != context.toggled.contains(&path);
+        let highlighted = collapsible && context.highlighted_group.as_ref() == Some(&path);
+        // The header is reachable with the keyboard, through the `aria-activedescendant` of the trigger, whenever
+        // it can be expanded and collapsed.
+        let header_id = collapsible.then(|| group_id(&context.list_id, &path));
+
+        let toggle = {
+            let path = path.clone();
+            let toggle_group = context.toggle_group.clone();
+            Callback::from(move |event: MouseEvent| {
+                event.stop_propagation();
+                toggle_group.emit(path.clone());
+            })
+        };
+        let onclick = {
+            let path = path.clone();
+            let select_group = context.select_group.clone();
+            let toggle = toggle.clone();
+            Callback::from(move |event: MouseEvent| {
+                if tree {
+                    select_group.emit((path.clone(), ChangeSource::OptionClick));
+                } else if collapsible {
+                    toggle.emit(event);
+                }
+            })
+        };
+
+        html! {
+            <li
+                id={header_id.clone().filter(|_| tree)}
+                role={if tree { "treeitem" } else { "group" }}
+                aria-label={label.clone()}
+                aria-level={tree.then(|| (depth + 1).to_string())}
+                aria-expanded={tree.then(|| (!collapsed).to_string())}
+                aria-selected={tree.then(|| fully_selected.to_string())}
+                class={classes!("selectrs-group", class)}
+                style={style}
+                data-state={collapsible.then_some(if collapsed { "collapsed" } else { "expanded" })}
+                data-selected={data_flag(tree && fully_selected)}
+            >
+                <div
+                    id={header_id.filter(|_| !tree)}
+                    class={classes!("selectrs-group-header", header_class)}
+                    style={join_styles(&[&if tree { indent_style(indent, depth) } else { String::new() }, header_style])}
+                    role={(collapsible && !tree).then_some("button")}
+                    aria-expanded={(collapsible && !tree).then(|| (!collapsed).to_string())}
+                    onclick={onclick}
+                    data-state={collapsible.then_some(if collapsed { "collapsed" } else { "expanded" })}
+                    data-selected={data_flag(tree && fully_selected)}
+                    data-highlighted={data_flag(highlighted)}
+                >
+                    if tree {
+                        <span class={classes!("selectrs-group-toggle", toggle_class)} style={toggle_style} aria-hidden="true" onclick={toggle}>
+                            { if collapsed { "▸" } else { "▾" } }
+                        </span>
+                    }
+                    { label }
+                    if count > 0 {
+                        <span class={classes!("selectrs-group-count", count_class)} style={count_style}>{ count }</span>
+                    }
+                </div>
+                // Collapsed options are only hidden, so that they remain registered.
+                <ul
+                    class="selectrs-group-options"
+                    role={if tree { "group" } else { "none" }}
+                    style={collapsed.then_some("display: none;")}
+                >
+                    { items }
+                </ul>
+            </li>
+        }
+    } else if group && depth == 0 {
+        html! {
+            <optgroup label={label} class={classes!("selectrs-group", class)} style={style}>
+                { items }
+            </optgroup>
+        }
+    } else {
+        // Native `optgroup` elements cannot be nested, so nested groups are flattened.
+        items
+    }
+}
+
+/// Properties for configuring the `Option` component.
+///
+/// The `Option` component represents an individual selectable option within a group of options, such as a
+/// string, a radio button, checkbox, or a custom select element. It allows for customization of the option's value,
+/// label, selection state, and appearance. The component also supports event handling for user interactions
+/// (e.g., click events).
+#[derive(Properties, PartialEq, Clone)]
+pub struct OptionProps {
+    /// The value of the option.
+    ///
+    /// This is the underlying value associated with the option. It is typically used when the user selects
+    /// this option, and is submitted or processed based on the selected state of the option. Defaults to an
+    /// empty string if not provided.
+    #[prop_or_default]
+    pub value: AttrValue,
+
+    /// The label displayed for the option.
+    ///
+    /// This property defines the content that is shown to the user as the label for the option. It can be
+    /// any valid child element, such as text, icons, or other components. Defaults to no label (empty).
+    #[prop_or_default]
+    pub label: Children,
+
+    /// A secondary line of text describing the option.
+    ///
+    /// In custom mode, it is displayed below the label and announced as the description of the option. Native
+    /// options show it as a tooltip through their `title` attribute. The description is also matched when
+    /// searching options. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub description: &'static str,
+
+    /// An icon displayed before the label in custom mode.
+    ///
+    /// The icon is decorative and hidden from assistive technologies. Native options cannot render markup
+    /// and omit it. Defaults to no icon (empty) if not provided.
+    #[prop_or_default]
+    pub icon: Html,
+
+    /// A short badge displayed after the label, such as "New" or a count.
+    ///
+    /// In custom mode, it is rendered as a separate element at the end of the option. Native options append
+    /// it to their label in parentheses. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub badge: &'static str,
+
+    /// Additional terms matched when searching options, such as synonyms or abbreviations.
+    ///
+    /// Keywords are never displayed. Defaults to an empty list if not provided.
+    #[prop_or_default]
+    pub keywords: Vec<String>,
+
+    /// Whether the option is selected.
+    ///
+    /// This property indicates if the option is currently selected. If set to `true`, the option is visually
+    /// marked as selected, and it may trigger related behavior such as updating state or submitting a form.
+    /// Inside a `Select`, this is read from the `SelectContext`. Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub selected: bool,
+
+    /// Whether the option is highlighted with the keyboard (custom mode only).
+    ///
+    /// Inside a `Select`, this is read from the `SelectContext` while the user navigates the custom popup list
+    /// with the arrow keys.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub highlighted: bool,
+
+    /// Whether the option is rendered inside a custom popup list.
+    ///
+    /// If set to `true`, the option is rendered as an element with the `option` role instead of a native
+    /// `option` element. Inside a `Select`, this is read from the `SelectContext`. Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub custom: bool,
+
+    /// Whether the option is rendered as a leaf of a tree select.
+    ///
+    /// Inside a `Select`, this is read from the `SelectContext`. Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub tree: bool,
+
+    /// The nesting level of the option in tree mode.
+    ///
+    /// Inside a `Select`, this is read from the `SelectContext` and used to indent the option. Defaults to `0` if
+    /// not provided.
+    #[prop_or_default]
+    pub depth: usize,
+
+    /// The indentation applied per nesting level in tree mode.
+    ///
+    /// Inside a `Select`, this is read from the `SelectContext`. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub indent: &'static str,
+
+    /// The id of the option element.
+    ///
+    /// In custom mode this is derived from the `SelectContext` inside a `Select`, so the highlighted option can
+    /// be referenced with `aria-activedescendant`. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub id: String,
+
+    /// Whether the option is disabled.
+    ///
+    /// If set to `true`, the option is considered disabled, meaning it cannot be interacted with by the user.
+    /// Disabled options may be visually different (e.g., grayed out). Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub disabled: bool,
+
+    /// Callback for when the option is clicked.
+    ///
+    /// This callback is invoked when the user clicks on the option. It can be used to trigger actions such as
+    /// updating the selected state or performing any other interaction. Inside a `Select`, the clicked option is
+    /// also selected or deselected through the `SelectContext`. Defaults to a no-op (no action).
+    #[prop_or_default]
+    pub on_click: Callback<()>,
+
+    /// Custom CSS class for the option.
+    ///
+    /// This property allows you to specify a custom CSS class for the option. This class is applied to the
+    /// individual option container, enabling you to style it differently from other options. Defaults to no
+    /// classes if not provided.
+    #[prop_or_default]
+    pub class: Classes,
+
+    /// Inline styles for the option.
+    ///
+    /// This property enables you to apply inline styles directly to the option element. It allows for precise
+    /// customization of the option's appearance without needing an external stylesheet. Defaults to an empty string
+    /// if not provided.
+    #[prop_or_default]
+    pub style: &'static str,
+
+    /// Custom class for a selected option.
+    ///
+    /// This property defines a custom CSS class that is applied when the option is selected. It enables you to
+    /// style the selected option differently, such as changing its background color or text style. Defaults to an
+    /// empty string if not provided.
+    #[prop_or_default]
+    pub selected_class: Classes,
+
+    /// Inline styles for a selected option.
+    ///
+    /// This property defines inline styles applied when the option is selected. It provides direct control over
+    /// the selected state styling, allowing for unique visual differentiation between selected and non-selected
+    /// options. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub selected_style: &'static str,
+
+    /// Custom class for a highlighted option (custom mode only).
+    ///
+    /// This property defines a custom CSS class that is applied while the option is highlighted with the
+    /// keyboard in the custom popup list. Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub highlighted_class: Classes,
+
+    /// Inline styles for a highlighted option (custom mode only).
+    ///
+    /// This property defines inline styles applied while the option is highlighted with the keyboard in
+    /// the custom popup list. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub highlighted_style: &'static str,
+}
+
+#[function_component(Option)]
+pub fn option(props: &OptionProps) -> Html {
+    let context = use_context::<SelectContext>();
+    let id = use_state(|| NEXT_ITEM_ID.fetch_add(1, Ordering::Relaxed));
+    let node = use_node_ref();
+
+    // Registers the option with the surrounding `Select`, which displays its label in the chips, searches it and
+    // lets the keyboard reach it.
+    {
+        let context = context.clone();
+        let (id, node) = (*id, node.clone());
+        use_effect_with(props.clone(), move |props| {
+            if let Some(context) = &context {
+                context.register_option(id, Rc::new(props.clone()), node);
+            }
+            move || {
+                if let Some(context) = context {
+                    context.unregister(id);
+                }
+            }
+        });
+    }
+
+    let Some(context) = context else {
+        return render_option(props, node, false);
+    };
+    // When the options are sorted, another option of the group may be displayed in the place of this one.
+    let mut props = context
+        .shown
+        .get(&*id)
+        .map_or_else(|| props.clone(), |shown| (**shown).clone());
+    let value = props.value.clone();
+    props.selected = context.is_selected(&value);
+    props.highlighted = context.custom && context.is_highlighted(&value);
+    props.custom = context.custom;
+    props.tree = context.tree;
+    props.indent = context.indent;
+    props.depth = context.depth;
+    if context.custom && !context.hidden {
+        props.id = option_id(&context.list_id, &value);
+    }
+    if context.clickable {
+        let select = context.select.clone();
+        let on_click = props.on_click.clone();
+        props.on_click = Callback::from(move |_| {
+            select.emit(value.to_string());
+            on_click.emit(());
+        });
+    }
+    render_option(&props, node, context.hidden)
+}
+
+/// An option rendered by a `Select` outside of its group, in the search results or a section.
+#[function_component(DetachedOption)]
+fn detached_option(props: &OptionProps) -> Html {
+    render_option(props, NodeRef::default(), false)
+}
+
+/// Renders the element of an option into `node`, hiding it when `hidden` is set.
+fn render_option(props: &OptionProps, node: NodeRef, hidden: bool) -> Html {
+    let OptionProps {
+        value,
+        label,
+        description,
+        icon,
+        badge,
+        selected,
+        highlighted,
+        custom,
+        tree,
+        depth,
+        indent,
+        id,
+        disabled,
+        on_click,
+        class,
+        style,
+        selected_style,
+        selected_class,
+        highlighted_class,
+        highlighted_style,
+        ..
+    } = props.clone();
+
+    let handle_click = {
+        let on_click = on_click.clone();
+        Callback::from(move |_| on_click.emit(()))
+    };
+
+    if custom {
+        let description_id =
+            (!id.is_empty() && !description.is_empty()).then(|| format!("{}-description", id));
+        let rich = !description.is_empty() || !badge.is_empty() || icon != html! {};
+        return html! {
+            <li
+                ref={node}
+                aria-describedby={description_id.clone()}
+                id={id}
+                role={if tree { "treeitem" } else { "option" }}
+                aria-level={tree.then(|| (depth + 1).to_string())}
+                class={classes!(
+                    "selectrs-option",
+                    class,
+                    selected.then_some(selected_class),
+                    highlighted.then_some(highlighted_class)
+                )}
+                style={join_styles(&[
+                    if hidden { "display: none;" } else { "" },
+                    &if tree { indent_style(indent, depth) } else { String::new() },
+                    style,
+                    if selected { selected_style } else { "" },
+                    if highlighted { highlighted_style } else { "" },
+                ])}
+                aria-selected={selected.to_string()}
+                aria-disabled={disabled.to_string()}
+                data-state={selection_state(selected)}
+                data-selected={data_flag(selected)}
+                data-disabled={data_flag(disabled)}
+                data-highlighted={data_flag(highlighted)}
+                onclick={move |ev: MouseEvent| {
+                    if !disabled {
+                        handle_click.emit(ev);
+                    }
+                }}
+            >
+                if rich {
+                    if icon != html! {} {
+                        <span class="selectrs-option-icon" aria-hidden="true">{ icon }</span>
+                    }
+                    <span class="selectrs-option-content">
+                        <span class="selectrs-option-label">{ label }</span>
+                        if !description.is_empty() {
+                            <span id={description_id} class="selectrs-option-description">
+                                { description }
+                            </span>
+                        }
+                    </span>
+                    if !badge.is_empty() {
+                        <span class="selectrs-option-badge">{ badge }</span>
+                    }
+                } else {
+                    { label }
+                }
+            </li>
+        };
+    }
+
+    html! {
+        <option
+            ref={node}
+            value={value}
+            class={classes!("selectrs-option", class, selected.then_some(selected_class))}
+            style={join_styles(&[
+                if hidden { "display: none;" } else { "" },
+                style,
+                if selected { selected_style } else { "" },
+            ])}
+            onclick={move |ev: MouseEvent| {
+                ev.prevent_default();
+                handle_click.emit(ev);
+            }}
+            disabled={disabled}
+            title={(!description.is_empty()).then_some(description)}
+            data-state={selection_state(selected)}
+            data-selected={data_flag(selected)}
+            data-disabled={data_flag(disabled)}
+        >
+            { label }
+            if !badge.is_empty() {
+                { format!(" ({})", badge) }
+            }
+        </option>
+    }
+}
+
+/// An option of a `Cascader` level.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CascaderOption {
+    /// The value of the option, used as a segment of the selected path.
+    pub value: String,
+    /// The text displayed for the option.
+    pub label: String,
+    /// Whether the option cannot be chosen.
+    pub disabled: bool,
+    /// Whether the option ends the path early, so that no further level is loaded after it.
+    pub leaf: bool,
+}
+
+impl CascaderOption {
+    /// Creates an enabled option with the given value and label.
+    pub fn new(value: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A request to load the options of a `Cascader` level.
+///
+/// Loaders receive the values chosen in the previous levels and answer by emitting the options on
+/// `resolve`. They may do so asynchronously, e.g. after fetching the options from a server; results
+/// for a path that is no longer selected are ignored.
+#[derive(Clone, PartialEq)]
+pub struct CascaderRequest {
+    /// The index of the level to load, starting at `0`.
+    pub level: usize,
+    /// The values chosen in the previous levels.
+    pub path: Vec<String>,
+    /// Callback to emit the loaded options on.
+    pub resolve: Callback<Vec<CascaderOption>>,
+}
+
+/// Controls the text a `Cascader` displays for the selected path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CascaderDisplay {
+    /// Display the labels of every level, joined by the separator.
+    #[default]
+    FullPath,
+    /// Display the label of the last chosen level only.
+    Leaf,
+}
+
+/// Properties for configuring the `Cascader` component.
+///
+/// The `Cascader` component selects a path of dependent values, such as Country → State → City,
+/// where the options of each level depend on the choice made in the previous one. Every level is
+/// rendered as a column of the popup and its options are loaded lazily once the previous level
+/// has been chosen.
+#[derive(Properties, PartialEq, Clone)]
+pub struct CascaderProps {
+    /// The name of the cascader.
+    ///
+    /// When set, every value of the selected path is submitted with forms under this name, in order from
+    /// the first level down. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub name: AttrValue,
+
+    /// The id of the cascader trigger.
+    ///
+    /// It helps in uniquely identifying the component within the DOM. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub id: AttrValue,
+
+    /// The form to associate the cascader with.
+    ///
+    /// The value must be the `id` of a form element in the same document. If not provided, the cascader is
+    /// associated with its nearest ancestor form. Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub form: &'static str,
+
+    /// The placeholder text displayed while no value is chosen.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub placeholder: AttrValue,
+
+    /// Whether the cascader is disabled.
+    ///
+    /// If set to `true`, the popup cannot be opened. Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub disabled: bool,
+
+    /// The option loaders, one per level.
+    ///
+    /// The number of loaders defines the depth of the cascader. The loader of the first level is called
+    /// on mount and the loader of each following level is called whenever a value is chosen in the
+    /// previous one. When the loaders change, every displayed level is loaded again from the new loaders
+    /// and answers of the previous ones are ignored, so loaders capturing state should be memoized, e.g.
+    /// with `use_callback`. Defaults to no levels if not provided.
+    #[prop_or_default]
+    pub loaders: Vec<Callback<CascaderRequest>>,
+
+    /// Callback triggered when a complete path is chosen.
+    ///
+    /// It receives the values chosen from the first level down. A path is complete when its length equals
+    /// the number of loaders or its last option is a leaf. Resetting the owning form emits an empty path.
+    /// Defaults to a no-op if not provided.
+    #[prop_or_default]
+    pub onchange: Callback<Vec<String>>,
+
+    /// Whether `onchange` is also emitted, and the path also submitted with forms, while it is incomplete.
+    ///
+    /// When set, every choice emits the path chosen so far, down to the level that was just chosen.
+    /// Defaults to `false` if not provided.
+    #[prop_or_default]
+    pub change_on_select: bool,
+
+    /// Which labels are displayed for the selected path.
+    ///
+    /// Defaults to `CascaderDisplay::FullPath` if not provided.
+    #[prop_or_default]
+    pub display: CascaderDisplay,
+
+    /// The separator placed between labels when displaying the full path.
+    ///
+    /// Defaults to `" / "` if not provided.
+    #[prop_or(" / ")]
+    pub separator: &'static str,
+
+    /// Custom CSS class for the cascader container.
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub class: Classes,
+
+    /// Inline styles for the cascader container.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub style: &'static str,
+
+    /// Custom CSS class for the trigger displaying the selected path.
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub select_class: Classes,
+
+    /// Inline styles for the trigger displaying the selected path.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub select_style: &'static str,
+
+    /// Custom CSS class for the popup holding the columns.
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub popup_class: Classes,
+
+    /// Inline styles for the popup holding the columns.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub popup_style: &'static str,
+
+    /// Custom CSS class for each column.
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub column_class: Classes,
+
+    /// Inline styles for each column.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub column_style: &'static str,
+
+    /// Custom CSS class for each option of a column.
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub item_class: Classes,
+
+    /// Inline styles for each option of a column.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub item_style: &'static str,
+
+    /// Custom CSS class for the options that are part of the selected path.
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub active_class: Classes,
+
+    /// Inline styles for the options that are part of the selected path.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub active_style: &'static str,
+
+    /// Custom CSS class for the option highlighted with the keyboard.
+    ///
+    /// Defaults to no classes if not provided.
+    #[prop_or_default]
+    pub highlighted_class: Classes,
+
+    /// Inline styles for the option highlighted with the keyboard.
+    ///
+    /// Defaults to an empty string if not provided.
+    #[prop_or_default]
+    pub highlighted_style: &'static str,
+
+    /// The built-in strings and the language of this instance.
+    ///
+    /// This overrides the locale provided by an enclosing `SelectLocaleProvider`. Defaults to the provided
+    /// locale, or to `SelectLocale::default()` with English strings if none is provided.
+    #[prop_or_default]
+    pub locale: std::option::Option<SelectLocale>,
+
+    /// The writing direction of the component.
+    ///
+    /// In `Direction::Rtl` the layout is mirrored, the left and right arrow keys follow the visual direction
+    /// and a floating popup aligns with the right edge of the trigger. Defaults to the direction provided by an
+    /// enclosing `ContextProvider<Direction>`, or to `Direction::Ltr` if none is provided.
+    #[prop_or_default]
+    pub dir: std::option::Option<Direction>,
+}
+
+/// The selected path of a `Cascader` and the options loaded for each of its levels.
+#[derive(Clone, Default, PartialEq)]
+struct CascaderState {
+    path: Vec<String>,
+    /// The options of each level, `None` while they are loading.
+    columns: Vec<std::option::Option<Vec<CascaderOption>>>,
+}
+
+impl CascaderState {
+    /// Returns whether the path is complete, i.e. no further level is displayed after its last value.
+    fn complete(&self) -> bool {
+        !self.path.is_empty() && self.columns.len() == self.path.len()
+    }
+}
+
+enum CascaderAction {
+    /// The options of `level` have been loaded for the given parent `path`.
+    Loaded {
+        level: usize,
+        path: Vec<String>,
+        options: Vec<CascaderOption>,
+    },
+    /// A value has been chosen at `level`, dropping the deeper levels.
+    Choose {
+        level: usize,
+        value: String,
+        load_next: bool,
+    },
+    /// The loaders have changed to the given number of `levels`, so every displayed level is loading again.
+    Reload { levels: usize },
+    /// The owning form has been reset, dropping the whole path.
+    Reset,
+}
+
+impl Reducible for CascaderState {
+    type Action = CascaderAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+        match action {
+            CascaderAction::Loaded {
+                level,
+                path,
+                options,
+            } => {
+                // Ignore results for a parent path that is no longer selected.
+                if level >= state.columns.len() || state.path.get(..level) != Some(&path[..]) {
+                    return self;
+                }
+                state.columns[level] = Some(options);
+            }
+            CascaderAction::Choose {
+                level,
+                value,
+                load_next,
+            } => {
+                state.path.truncate(level);
+                state.path.push(value);
+                state.columns.truncate(level + 1);
+                if load_next {
+                    state.columns.push(None);
+                }
+            }
+            CascaderAction::Reload { levels } => {
+                state.path.truncate(levels);
+                state.columns.truncate(levels.max(1));
+                state.columns.iter_mut().for_each(|column| *column = None);
+            }
+            CascaderAction::Reset => {
+                state.path.clear();
+                state.columns.truncate(1);
+            }
+        }
+        Rc::new(state)
+    }
+}
+
+/// Cascader Component
+///
+/// A Yew component for choosing a path of dependent values, such as a Country → State → City address picker.
+/// Each level is displayed as a column of the popup, and the options of a level are requested from its loader
+/// once a value has been chosen in the previous level.
+///
+/// # Properties
+/// The component uses the `CascaderProps` struct for its properties. Key properties include:
+///
+/// - **name**: The name under which the path values are submitted with forms (`AttrValue`). Default: `""`.
+/// - **form**: The ID of the form the cascader is associated with (`&'static str`). Default: `""`.
+/// - **loaders**: One loader per level, answering `CascaderRequest`s (`Vec<Callback<CascaderRequest>>`). Default: `[]`.
+/// - **onchange**: Callback receiving the complete selected path (`Callback<Vec<String>>`). Default: no-op.
+/// - **change_on_select**: Whether incomplete paths are emitted and submitted as well (`bool`). Default: `false`.
+/// - **display**: Whether to display the full path or only the leaf (`CascaderDisplay`). Default: `CascaderDisplay::FullPath`.
+/// - **separator**: The separator between labels of the full path (`&'static str`). Default: `" / "`.
+/// - **placeholder**: Text displayed while nothing is chosen (`AttrValue`). Default: `""`.
+/// - **disabled**: Whether the cascader is disabled (`bool`). Default: `false`.
+/// - **locale**: The built-in strings of this instance, e.g. the loading message (`Option<SelectLocale>`). Default: the provided locale.
+/// - **dir**: The writing direction, mirroring the columns and arrow keys (`Option<Direction>`). Default: the provided direction.
+///
+/// # Example
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Cascader, CascaderDisplay, CascaderOption, CascaderRequest};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let countries = Callback::from(|request: CascaderRequest| {
+///         request.resolve.emit(vec![
+///             CascaderOption::new("us", "United States"),
+///             CascaderOption::new("ca", "Canada"),
+///         ]);
+///     });
+///     let states = Callback::from(|request: CascaderRequest| {
+///         let options = match request.path[0].as_str() {
+///             "us" => vec![CascaderOption::new("ca", "California"), CascaderOption::new("ny", "New York")],
+///             _ => vec![CascaderOption::new("on", "Ontario"), CascaderOption::new("qc", "Quebec")],
+///         };
+///         request.resolve.emit(options);
+///     });
+///
+///     html! {
+///         <Cascader
+///             placeholder="Select a region..."
+///             loaders={vec![countries, states]}
+///             display={CascaderDisplay::FullPath}
+///             onchange={Callback::from(|path: Vec<String>| {
+///                 log::info!("Selected path: {:?}", path);
+///             })}
+///         />
+///     }
+/// }
+/// ```
+///
+/// # Behavior
+/// - Choosing an option drops the deeper levels and loads the next one, unless the option is a `leaf` or the
+///   last level has been reached, in which case the path is complete and the popup closes.
+/// - `onchange` is emitted once a complete path is chosen, or on every choice with `change_on_select`.
+/// - The loader of the first level runs on mount, and every displayed level is loaded again when the loaders
+///   change.
+/// - When `name` is set, the selected path is submitted with forms as one hidden input per level once it is
+///   complete, and resetting the owning form clears the path, emitting an empty path through `onchange` if one
+///   was chosen.
+/// - The popup is a single `listbox` holding one group of options per level, and shares its trigger, keyboard
+///   handling and option markup with the custom popup of `Select`. Up, down, `Home` and `End` move within a
+///   column, right, `Enter` and `Space` choose the highlighted option and left moves back to the previous
+///   column.
+#[function_component(Cascader)]
+pub fn cascader(props: &CascaderProps) -> Html {
+    let CascaderProps {
+        name,
+        id,
+        form,
+        placeholder,
+        disabled,
+        loaders,
+        onchange,
+        change_on_select,
+        display,
+        separator,
+        class,
+        style,
+        select_class,
+        select_style,
+        popup_class,
+        popup_style,
+        column_class,
+        column_style,
+        item_class,
+        item_style,
+        active_class,
+        active_style,
+        highlighted_class,
+        highlighted_style,
+        locale,
+        dir,
+    } = props.clone();
+
+    let locale = use_locale(locale);
+    let direction = use_direction(dir);
+    let state = use_reducer(|| CascaderState {
+        path: Vec::new(),
+        columns: vec![None],
+    });
+    let open = use_state(|| false);
+    let highlighted = use_state(|| None::<(usize, String)>);
+    let list_id = use_list_id(&id);
+    let wrapper_ref = use_node_ref();
+    let trigger_ref = use_node_ref();
+    // Incremented whenever the loaders change, so that the answers of the previous loaders are ignored.
+    let generation = use_mut_ref(|| 0_usize);
+
+    let load = {
+        let dispatcher = state.dispatcher();
+        let loaders = loaders.clone();
+        let generation = generation.clone();
+        move |level: usize, path: Vec<String>| {
+            if let Some(loader) = loaders.get(level) {
+                let dispatcher = dispatcher.clone();
+                let generation = generation.clone();
+                let requested = *generation.borrow();
+                let parent = path.clone();
+                loader.emit(CascaderRequest {
+                    level,
+                    path,
+                    resolve: Callback::from(move |options| {
+                        if *generation.borrow() == requested {
+                            dispatcher.dispatch(CascaderAction::Loaded {
+                                level,
+                                path: parent.clone(),
+                                options,
+                            })
+                        }
+                    }),
+                });
+            }
+        }
+    };
+
+    {
+        let load = load.clone();
+        let state = state.clone();
+        use_effect_with(loaders.clone(), move |loaders| {
+            *generation.borrow_mut() += 1;
+            let levels = loaders.len();
+            state.dispatch(CascaderAction::Reload { levels });
+            for level in 0..state.columns.len().min(levels) {
+                load(level, state.path[..level].to_vec());
+            }
+        });
+    }
+
+    let choose = {
+        let state = state.clone();
+        let open = open.clone();
+        let highlighted = highlighted.clone();
+        let onchange = onchange.clone();
+        let levels = loaders.len();
+        Callback::from(move |(level, option): (usize, CascaderOption)| {
+            if option.disabled {
+                return;
+            }
+            let mut path = state.path[..level.min(state.path.len())].to_vec();
+            path.push(option.value.clone());
+            let load_next = !option.leaf && level + 1 < levels;
+            state.dispatch(CascaderAction::Choose {
+                level,
+                value: option.value,
+                load_next,
+            });
+            highlighted.set(None);
+            if load_next {
+                load(level + 1, path.clone());
+            } else {
+                open.set(false);
+            }
+            if !load_next || change_on_select {
+                onchange.emit(path);
+            }
+        })
+    };
+
+    let reset = {
+        let state = state.clone();
+        let open = open.clone();
+        let highlighted = highlighted.clone();
+        Callback::from(move |_| {
+            let chosen = !state.path.is_empty();
+            state.dispatch(CascaderAction::Reset);
+            open.set(false);
+            highlighted.set(None);
+            if chosen {
+                onchange.emit(Vec::new());
+            }
+        })
+    };
+    use_form_reset(wrapper_ref.clone(), form, reset);
+
+    let column_options = |level: usize| -> Vec<CascaderOption> {
+        state
+            .columns
+            .get(level)
+            .cloned()
+            .flatten()
+            .unwrap_or_default()
+    };
+
+    let labels: Vec<String> = state
+        .path
+        .iter()
+        .enumerate()
+        .map(|(level, value)| {
+            column_options(level)
+                .into_iter()
+                .find(|option| &option.value == value)
+                .map_or_else(|| value.clone(), |option| option.label)
+        })
+        .collect();
+    let text = match display {
+        CascaderDisplay::FullPath => labels.join(separator),
+        CascaderDisplay::Leaf => labels.last().cloned().unwrap_or_default(),
+    };
+
+    let toggle_open = toggle_popup(&open, disabled);
+
+    let onkeydown = {
+        let open = open.clone();
+        let highlighted = highlighted.clone();
+        let choose = choose.clone();
+        let trigger_ref = trigger_ref.clone();
+        let path = state.path.clone();
+        let columns: Vec<Vec<CascaderOption>> =
+            (0..state.columns.len()).map(column_options).collect();
+        Callback::from(move |event: KeyboardEvent| {
+            if disabled {
+                return;
+            }
+            let key = logical_arrow(event.key(), direction);
+            let (level, current) = match &*highlighted {
+                Some((level, value)) => (*level, Some(value.clone())),
+                None => (path.len().min(columns.len().saturating_sub(1)), None),
+            };
+            let enabled: Vec<&CascaderOption> = columns
+                .get(level)
+                .map(|options| options.iter().filter(|option| !option.disabled).collect())
+                .unwrap_or_default();
+            let position = current
+                .as_ref()
+                .and_then(|value| enabled.iter().position(|option| &option.value == value));
+            match key.as_str() {
+                "ArrowDown" | "ArrowUp" | "Home" | "End" => {
+                    event.prevent_default();
+                    if let Some(next) = step_highlight(&key, position, enabled.len()) {
+                        open.set(true);
+                        highlighted.set(Some((level, enabled[next].value.clone())));
+                    }
+                }
+                "ArrowRight" | "Enter" | " " => {
+                    event.prevent_default();
+                    match position {
+                        Some(index) if *open => choose.emit((level, enabled[index].clone())),
+                        _ if key == "ArrowRight" => {}
+                        _ => open.set(!*open),
+                    }
+                }
+                "ArrowLeft" if level > 0 => {
+                    event.prevent_default();
+                    highlighted.set(path.get(level - 1).map(|value| (level - 1, value.clone())));
+                }
+                _ => close_popup(&event, &open, &trigger_ref),
+            }
+        })
+    };
 
-    /// The currently selected option in the group.
-    ///
-    /// This represents the selected value within the group. It should be bound to a state to
-    /// reflect changes as the user selects different options. Defaults to an empty string if not provided.
-    #[prop_or_default]
-    pub selected: String,
+    let onfocusout = {
+        let open = open.clone();
+        let wrapper_ref = wrapper_ref.clone();
+        Callback::from(move |event: FocusEvent| {
+            if focus_crosses(&event, &wrapper_ref) {
+                open.set(false);
+            }
+        })
+    };
 
-    /// Callback for when the selected option changes.
+    let item_id = |level: usize, value: &str| option_id(&format!("{}-{}", *list_id, level), value);
+
+    // The options of every level are rendered like the options of the custom popup of a `Select`.
+    let render_item = |level: usize, option: &CascaderOption| {
+        let selected = state.path.get(level) == Some(&option.value);
+        let props = yew::props!(OptionProps {
+            value: AttrValue::from(option.value.clone()),
+            label: Children::new(vec![html! { option.label.clone() }]),
+            selected,
+            highlighted: (*highlighted)
+                .as_ref()
+                .is_some_and(|(l, v)| *l == level && *v == option.value),
+            custom: true,
+            id: item_id(level, &option.value),
+            disabled: option.disabled,
+            on_click: choose.reform({
+                let option = option.clone();
+                move |_| (level, option.clone())
+            }),
+            class: item_class.clone(),
+            style: item_style,
+            selected_class: active_class.clone(),
+            selected_style: active_style,
+            highlighted_class: highlighted_class.clone(),
+            highlighted_style,
+        });
+        render_option(&props, NodeRef::default(), false)
+    };
+
+    html! {
+        <div
+            class={classes!("selectrs", "selectrs-cascader", class)}
+            style={style}
+            ref={wrapper_ref}
+            dir={direction.as_str()}
+            onfocusout={onfocusout}
+            data-state={open_state(*open)}
+            data-disabled={data_flag(disabled)}
+            data-placeholder={data_flag(state.path.is_empty())}
+        >
+            <div
+                ref={trigger_ref}
+                id={id}
+                role="combobox"
+                tabindex={if disabled { "-1" } else { "0" }}
+                class={classes!("selectrs-trigger", select_class)}
+                style={select_style}
+                aria-haspopup="listbox"
+                aria-expanded={open.to_string()}
+                aria-controls={(*list_id).clone()}
+                aria-disabled={disabled.to_string()}
+                aria-activedescendant={(*highlighted).as_ref().filter(|_| *open).map(|(level, value)| item_id(*level, value))}
+                onclick={toggle_open}
+                onkeydown={onkeydown}
+                data-state={open_state(*open)}
+                data-disabled={data_flag(disabled)}
+                data-placeholder={data_flag(text.is_empty())}
+            >
+                { if text.is_empty() { placeholder.to_string() } else { text } }
+            </div>
+            if !name.is_empty() && (state.complete() || change_on_select) {
+                { for state.path.iter().map(|value| html! {
+                    <input type="hidden" name={name.clone()} form={form} value={value.clone()} disabled={disabled} />
+                }) }
+            }
+            if *open && !disabled {
+                <div
+                    id={(*list_id).clone()}
+                    role="listbox"
+                    class={classes!("selectrs-popup", "selectrs-cascader-popup", popup_class)}
+                    style={popup_style}
+                    onmousedown={|event: MouseEvent| event.prevent_default()}
+                    data-state="open"
+                >
+                    // Every level is a group of the listbox, labelled by the option chosen in the previous one.
+                    { for state.columns.iter().enumerate().map(|(level, column)| html! {
+                        <ul
+                            role="group"
+                            aria-label={level.checked_sub(1).and_then(|parent| labels.get(parent).cloned())}
+                            class={classes!("selectrs-cascader-column", column_class.clone())}
+                            style={column_style}
+                            aria-busy={column.is_none().to_string()}
+                            data-state={if column.is_none() { "loading" } else { "loaded" }}
+                        >
+                            if column.is_none() {
+                                <li class="selectrs-loading" role="presentation">{ locale.loading }</li>
+                            }
+                            { for column.iter().flatten().map(|option| render_item(level, option)) }
+                        </ul>
+                    }) }
+                </div>
+            }
+        </div>
+    }
+}
+
+/// Properties for configuring the `SelectThemeProvider` component.
+#[cfg(feature = "theme")]
+#[derive(Properties, PartialEq, Clone)]
+pub struct SelectThemeProviderProps {
+    /// The color scheme of the default theme.
     ///
-    /// This callback is triggered whenever the user selects a different option within the group.
-    /// The callback receives a string representing the newly selected option's value. Defaults to a no-op.
+    /// The variant is applied to every component rendered inside the provider, including popups rendered
+    /// into a portal. Defaults to `SelectTheme::Light` if not provided.
     #[prop_or_default]
-    pub onchange: Callback<String>,
+    pub theme: SelectTheme,
 
-    /// Child components of type `Option` for the group.
+    /// Whether to inject the default stylesheet into the document `head`.
     ///
-    /// This property allows you to pass one or more `Option` components as children of the `Group` component.
-    /// These `Option` components represent the individual selectable options within the group.
-    /// Defaults to an empty string if not provided.
-    #[prop_or_default]
-    pub children: ChildrenWithProps<Option>,
+    /// Set this to `false` for nested providers, or when the stylesheet from `selectrs::theme::STYLESHEET` is
+    /// bundled manually. Defaults to `true` if not provided.
+    #[prop_or(true)]
+    pub inject: bool,
 
-    /// Custom CSS class for the group.
+    /// Custom CSS class for the element wrapping the themed components.
     ///
-    /// This property allows for custom styling of the group container by specifying one or more CSS classes.
-    /// It is applied to the outer wrapper of the group, such as for styling the container element.
-    /// Defaults to an empty string if not provided.
+    /// Defaults to no classes if not provided.
     #[prop_or_default]
-    pub class: &'static str,
+    pub class: Classes,
 
-    /// Inline styles for the group.
+    /// Inline styles for the element wrapping the themed components.
     ///
-    /// This property allows for custom inline styles to be applied directly to the group container.
-    /// It provides more granular control over the styling of the group and its elements without the need for external CSS.
+    /// This is the place to override theme variables such as `--selectrs-accent` or `--selectrs-radius`.
     /// Defaults to an empty string if not provided.
     #[prop_or_default]
     pub style: &'static str,
+
+    /// The components to theme.
+    pub children: Html,
 }
 
-#[function_component(Group)]
-pub fn group(props: &GroupProps) -> Html {
-    let GroupProps {
-        label,
-        group,
-        selected,
-        onchange,
-        children,
+/// SelectThemeProvider Component
+///
+/// A component applying the built-in theme of the `theme` feature to every `Select` and `Cascader` rendered
+/// inside it. It injects the default stylesheet once into the document `head` and provides the chosen
+/// `SelectTheme` variant, so the components render with a usable look without any class props.
+///
+/// # Properties
+/// The component uses the `SelectThemeProviderProps` struct for its properties. Key properties include:
+///
+/// - **theme**: The color scheme of the theme (`SelectTheme`). Default: `SelectTheme::Light`.
+/// - **inject**: Whether to inject the default stylesheet into the document `head` (`bool`). Default: `true`.
+/// - **class**: Custom CSS class for the wrapping element (`Classes`). Default: empty.
+/// - **style**: Inline styles for the wrapping element, e.g. to override theme variables (`&'static str`). Default: `""`.
+///
+/// # Example
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group, SelectTheme, SelectThemeProvider};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     html! {
+///         <SelectThemeProvider theme={SelectTheme::Dark} style="--selectrs-accent: #16a34a;">
+///             <Select custom=true multiple=true placeholder="Pick some fruits...">
+///                 <Group>
+///                     <Option value="apple" label="Apple" />
+///                     <Option value="banana" label="Banana" />
+///                 </Group>
+///             </Select>
+///         </SelectThemeProvider>
+///     }
+/// }
+/// ```
+///
+/// # Notes
+/// - The theme is keyed on the stable `selectrs-*` class names, which are always rendered next to the
+///   classes passed through the class props, so both can be combined.
+/// - The wrapping element carries a `data-selectrs-theme` attribute defining the theme variables. Popups
+///   rendered into a portal carry the attribute themselves, so they do not inherit variables overridden
+///   through `style`; override them in a stylesheet targeting `[data-selectrs-theme]` instead.
+#[cfg(feature = "theme")]
+#[function_component(SelectThemeProvider)]
+pub fn select_theme_provider(props: &SelectThemeProviderProps) -> Html {
+    let SelectThemeProviderProps {
+        theme,
+        inject,
         class,
         style,
+        children,
     } = props.clone();
 
-    if group {
-        html! {
-            <optgroup label={label} class={class} style={style}>
-                { for children.iter().map(|mut child| {
-                    let props = Rc::make_mut(&mut child.props);
-                    let is_selected = props.value == selected;
-                    let onchange = onchange.clone();
-                    let value = props.value;
-
-                    props.selected = is_selected;
-                    props.on_click = Callback::from(move |_| {
-                        onchange.emit(value.to_string());
-                    });
-
-                    child
-                }) }
-            </optgroup>
-        }
-    } else {
-        html! {
-            { for children.iter().map(|mut child| {
-                    let props = Rc::make_mut(&mut child.props);
-                    let is_selected = props.value == selected;
-                    let onchange = onchange.clone();
-                    let value = props.value;
-
-                    props.selected = is_selected;
-                    props.on_click = Callback::from(move |_| {
-                        onchange.emit(value.to_string());
-                    });
+    let head = inject
+        .then(|| web_sys::window()?.document()?.head())
+        .flatten();
 
-                    child
-                }) }
-        }
+    html! {
+        <ContextProvider<SelectTheme> context={theme}>
+            if let Some(head) = head {
+                { create_portal(html! { <style>{ crate::theme::STYLESHEET }</style> }, head.into()) }
+            }
+            <div class={classes!("selectrs-theme", class)} style={style} data-selectrs-theme={theme.as_str()}>
+                { children }
+            </div>
+        </ContextProvider<SelectTheme>>
     }
 }
 
-/// Properties for configuring the `Option` component.
-///
-/// The `Option` component represents an individual selectable option within a group of options, such as a
-/// string, a radio button, checkbox, or a custom select element. It allows for customization of the option's value,
-/// label, selection state, and appearance. The component also supports event handling for user interactions
-/// (e.g., click events).
+/// Properties for configuring the `SelectLocaleProvider` component.
 #[derive(Properties, PartialEq, Clone)]
-pub struct OptionProps {
-    /// The value of the option.
-    ///
-    /// This is the underlying value associated with the option. It is typically used when the user selects
-    /// this option, and is submitted or processed based on the selected state of the option. Defaults to an
-    /// empty string if not provided.
-    #[prop_or_default]
-    pub value: &'static str,
-
-    /// The label displayed for the option.
+pub struct SelectLocaleProviderProps {
+    /// The built-in strings and the language of the components inside the provider.
     ///
-    /// This property defines the content that is shown to the user as the label for the option. It can be
-    /// any valid child element, such as text, icons, or other components. Defaults to no label (empty).
-    #[prop_or_default]
-    pub label: Children,
-
-    /// Whether the option is selected.
-    ///
-    /// This property indicates if the option is currently selected. If set to `true`, the option is visually
-    /// marked as selected, and it may trigger related behavior such as updating state or submitting a form.
-    /// Defaults to `false` if not provided.
-    #[prop_or_default]
-    pub selected: bool,
-
-    /// Whether the option is disabled.
-    ///
-    /// If set to `true`, the option is considered disabled, meaning it cannot be interacted with by the user.
-    /// Disabled options may be visually different (e.g., grayed out). Defaults to `false` if not provided.
+    /// Defaults to `SelectLocale::default()` with English strings if not provided.
     #[prop_or_default]
-    pub disabled: bool,
+    pub locale: SelectLocale,
 
-    /// Callback for when the option is clicked.
-    ///
-    /// This callback is invoked when the user clicks on the option. It can be used to trigger actions such as
-    /// updating the selected state or performing any other interaction. Defaults to a no-op (no action).
-    #[prop_or_default]
-    pub on_click: Callback<()>,
+    /// The components to localize.
+    pub children: Html,
+}
 
-    /// Custom CSS class for the option.
-    ///
-    /// This property allows you to specify a custom CSS class for the option. This class is applied to the
-    /// individual option container, enabling you to style it differently from other options. Defaults to an empty
-    /// string if not provided.
-    #[prop_or_default]
-    pub class: &'static str,
+/// SelectLocaleProvider Component
+///
+/// A component providing a `SelectLocale` to every `Select` and `Cascader` rendered inside it. The locale
+/// holds the built-in strings, such as the accessible label of the chip close button or the message of an
+/// empty popup, and the language used to sort and match option labels. Single components can still
+/// override it through their `locale` property.
+///
+/// # Properties
+/// The component uses the `SelectLocaleProviderProps` struct for its properties. Key properties include:
+///
+/// - **locale**: The built-in strings and language (`SelectLocale`). Default: English.
+///
+/// # Example
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{Select, Option, Group, SelectLocale, SelectLocaleProvider};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let turkish = SelectLocale {
+///         language: "tr",
+///         remove: "{} kaldır",
+///         no_results: "Sonuç yok",
+///         loading: "Yükleniyor…",
+///         ..SelectLocale::default()
+///     };
+///
+///     html! {
+///         <SelectLocaleProvider locale={turkish}>
+///             <Select custom=true multiple=true placeholder="Şehir seçin...">
+///                 <Group>
+///                     <Option value="istanbul" label="İstanbul" />
+///                     <Option value="izmir" label="İzmir" />
+///                 </Group>
+///             </Select>
+///         </SelectLocaleProvider>
+///     }
+/// }
+/// ```
+#[function_component(SelectLocaleProvider)]
+pub fn select_locale_provider(props: &SelectLocaleProviderProps) -> Html {
+    html! {
+        <ContextProvider<SelectLocale> context={props.locale.clone()}>
+            { props.children.clone() }
+        </ContextProvider<SelectLocale>>
+    }
+}
 
-    /// Inline styles for the option.
+/// Properties for configuring the `ConfigSelect` component.
+#[cfg(feature = "serde")]
+#[derive(Properties, PartialEq, Clone)]
+pub struct ConfigSelectProps {
+    /// The configuration of the select, e.g. deserialized from JSON.
     ///
-    /// This property enables you to apply inline styles directly to the option element. It allows for precise
-    /// customization of the option's appearance without needing an external stylesheet. Defaults to an empty string
-    /// if not provided.
-    #[prop_or_default]
-    pub style: &'static str,
+    /// It provides the options and groups, whether several values can be selected, the placeholder, the
+    /// limits on the number of selected values and the initial selection.
+    pub config: SelectConfig,
 
-    /// Custom class for a selected option.
+    /// Callback triggered when the selected values change.
     ///
-    /// This property defines a custom CSS class that is applied when the option is selected. It enables you to
-    /// style the selected option differently, such as changing its background color or text style. Defaults to an
-    /// empty string if not provided.
+    /// Defaults to a no-op callback if not provided.
     #[prop_or_default]
-    pub selected_class: &'static str,
+    pub onchange: Callback<Vec<String>>,
 
-    /// Inline styles for a selected option.
+    /// Callback triggered with the result of checking the limits of the configuration.
     ///
-    /// This property defines inline styles applied when the option is selected. It provides direct control over
-    /// the selected state styling, allowing for unique visual differentiation between selected and non-selected
-    /// options. Defaults to an empty string if not provided.
+    /// Defaults to a no-op callback if not provided.
     #[prop_or_default]
-    pub selected_style: &'static str,
+    pub onvalidate: Callback<Result<(), String>>,
 }
 
-#[function_component(Option)]
-pub fn option(props: &OptionProps) -> Html {
-    let OptionProps {
-        label,
-        selected,
-        disabled,
-        on_click,
-        class,
-        style,
-        selected_style,
-        selected_class,
-        ..
+/// ConfigSelect Component
+///
+/// A component rendering a `Select` from a `SelectConfig`, available with the `serde` feature. It lets
+/// pickers whose options are defined on a backend be rendered without writing `Group` and `Option` markup.
+///
+/// # Properties
+/// The component uses the `ConfigSelectProps` struct for its properties. Key properties include:
+///
+/// - **config**: The options, groups, limits and initial selection (`SelectConfig`). Required.
+/// - **onchange**: Callback triggered when the selected values change (`Callback<Vec<String>>`). Default: no-op.
+/// - **onvalidate**: Callback receiving the result of checking the limits (`Callback<Result<(), String>>`). Default: no-op.
+///
+/// # Example
+/// ```rust
+/// use yew::prelude::*;
+/// use selectrs::yew::{ConfigSelect, SelectConfig};
+///
+/// #[function_component(App)]
+/// pub fn app() -> Html {
+///     let config: SelectConfig = serde_json::from_str(r#"{
+///         "name": "languages",
+///         "placeholder": "Pick up to two languages...",
+///         "multiple": true,
+///         "custom": true,
+///         "max_selected": 2,
+///         "options": [
+///             { "value": "rust", "label": "Rust", "group": "Compiled" },
+///             { "value": "go", "label": "Go", "group": "Compiled" },
+///             { "value": "python", "label": "Python", "group": "Interpreted" }
+///         ],
+///         "selection": { "values": ["rust"] }
+///     }"#).unwrap();
+///
+///     html! { <ConfigSelect config={config} /> }
+/// }
+/// ```
+///
+/// # Notes
+/// - Options are rendered in groups in the order of `groups`, then of their first reference, with the options
+///   without a group in a last, unlabeled group.
+/// - `min_selected` and `max_selected` are passed on to the `Select`, which stops adding values at the maximum
+///   and reports a selection outside of the limits with the error messages of the locale, like
+///   `SelectConfig::validate`.
+#[cfg(feature = "serde")]
+#[function_component(ConfigSelect)]
+pub fn config_select(props: &ConfigSelectProps) -> Html {
+    let ConfigSelectProps {
+        config,
+        onchange,
+        onvalidate,
     } = props.clone();
 
-    let handle_click = {
-        let on_click = on_click.clone();
-        Callback::from(move |_| on_click.emit(()))
-    };
-
     html! {
-        <option
-            class={format!("{} {}", class, if selected { selected_class } else { "" })}
-            style={format!("{} {}", style, if selected { selected_style } else { "" })}
-            onclick={move |ev: MouseEvent| {
-                ev.prevent_default();
-                handle_click.emit(ev);
-            }}
-            disabled={disabled}
+        <Select
+            name={config.name.clone()}
+            id={config.id.clone()}
+            placeholder={config.placeholder.clone()}
+            multiple={config.multiple}
+            custom={config.custom}
+            searchable={config.searchable}
+            disabled={config.disabled}
+            min_selected={config.min_selected}
+            max_selected={config.max_selected}
+            max_visible_chips={config.max_visible_chips}
+            default_values={config.selection.values.clone()}
+            onchange={onchange}
+            onvalidate={onvalidate}
         >
-            { label }
-        </option>
+            { for config.grouped_options().into_iter().map(|(group, options)| {
+                let label = options.first().and_then(|option| option.group.clone());
+                let options = options.into_iter().map(|option| html! {
+                    <Option
+                        key={option.value.clone()}
+                        value={option.value.clone()}
+                        label={html! { option.label.clone() }}
+                        disabled={option.disabled}
+                    />
+                });
+                // Groups referenced by options without a definition have a label but no configuration.
+                match label {
+                    Some(label) => html! {
+                        <Group
+                            label={label}
+                            group=true
+                            collapsible={group.is_some_and(|group| group.collapsible)}
+                            default_collapsed={group.is_some_and(|group| group.default_collapsed)}
+                        >
+                            { for options }
+                        </Group>
+                    },
+                    None => html! { <Group>{ for options }</Group> },
+                }
+            }) }
+        </Select>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders `App` to an HTML string, without running effects.
+    async fn render<App: BaseComponent<Properties = ()>>() -> String {
+        yew::LocalServerRenderer::<App>::new()
+            .hydratable(false)
+            .render()
+            .await
+    }
+
+    #[test]
+    fn option_ids_are_valid_and_distinct() {
+        assert_eq!(option_id("list", "new-york"), "list-option-new-york");
+        assert_eq!(option_id("list", "New York"), "list-option-New_20_York");
+        assert_eq!(
+            option_id("list", "say \"hi\""),
+            "list-option-say_20__22_hi_22_"
+        );
+        assert_eq!(option_id("list", "é"), "list-option-_e9_");
+        assert_ne!(option_id("list", "a b"), option_id("list", "a_20_b"));
+        assert!(!option_id("list", "tab\tand\nnewline").contains(char::is_whitespace));
+    }
+
+    #[test]
+    fn persist_key_requires_persistence_and_a_name() {
+        assert_eq!(persist_key(&Persist::None, "status", "filters"), None);
+        assert_eq!(
+            persist_key(&Persist::Local, "status", "filters").as_deref(),
+            Some("selectrs:status")
+        );
+        assert_eq!(
+            persist_key(&Persist::Session, "", "filters").as_deref(),
+            Some("selectrs:filters")
+        );
+        assert_eq!(persist_key(&Persist::Local, "", ""), None);
+    }
+
+    #[test]
+    fn entries_are_the_same_while_their_props_are() {
+        let entry = |id: usize, props: &Rc<OptionProps>| OptionEntry {
+            id,
+            path: vec![0],
+            props: props.clone(),
+            node: NodeRef::default(),
+        };
+        let (open, closed) = (
+            Rc::new(yew::props!(OptionProps {})),
+            Rc::new(yew::props!(OptionProps {})),
+        );
+        let entries = vec![entry(1, &open), entry(2, &closed)];
+
+        assert!(same_entries(
+            &entries,
+            &[entry(1, &open), entry(2, &closed)]
+        ));
+        assert!(!same_entries(
+            &entries,
+            &[entry(1, &closed), entry(2, &open)]
+        ));
+        assert!(!same_entries(&entries, &[entry(1, &open)]));
+        // Props registered again are new, even when they are equal.
+        assert!(!same_entries(
+            &entries,
+            &[
+                entry(1, &Rc::new(yew::props!(OptionProps {}))),
+                entry(2, &closed)
+            ]
+        ));
+    }
+
+    #[tokio::test]
+    async fn groups_start_collapsed_before_registering() {
+        #[function_component(App)]
+        fn app() -> Html {
+            html! {
+                <Select custom=true>
+                    <Group label="Berries" group=true collapsible=true default_collapsed=true>
+                        <Option value="strawberry" label="Strawberry" />
+                    </Group>
+                    <Group label="Citrus" group=true collapsible=true>
+                        <Option value="lemon" label="Lemon" />
+                    </Group>
+                </Select>
+            }
+        }
+
+        let html = render::<App>().await;
+        assert_eq!(
+            html.matches(r#"data-state="collapsed" class="selectrs-group""#)
+                .count(),
+            1
+        );
+        assert_eq!(
+            html.matches(r#"data-state="expanded" class="selectrs-group""#)
+                .count(),
+            1
+        );
+        assert_eq!(html.matches(r#"style="display: none;""#).count(), 1);
+    }
+
+    #[test]
+    fn group_headers_are_navigable_before_their_options() {
+        let option =
+            |id: usize, path: Vec<usize>, value: &'static str, disabled: bool| OptionEntry {
+                id,
+                path,
+                props: Rc::new(yew::props!(OptionProps { value, disabled })),
+                node: NodeRef::default(),
+            };
+        let group = |path: Vec<usize>, collapsible: bool| {
+            (
+                path,
+                Rc::new(yew::props!(GroupProps {
+                    group: true,
+                    collapsible
+                })),
+            )
+        };
+        let entries = vec![
+            option(1, vec![10], "apple", false),
+            option(2, vec![10, 11], "lemon", false),
+            option(3, vec![10, 11], "lime", true),
+            option(4, vec![12], "kale", false),
+            option(5, vec![], "rice", false),
+        ];
+        let groups = vec![
+            group(vec![10], true),
+            group(vec![10, 11], true),
+            group(vec![12], false),
+        ];
+        let value = |value: &str| Highlight::Value(value.to_string());
+
+        assert_eq!(
+            navigable_items(&entries, &groups, &HashSet::new(), false),
+            vec![
+                Highlight::Group(vec![10]),
+                value("apple"),
+                Highlight::Group(vec![10, 11]),
+                value("lemon"),
+                value("kale"),
+                value("rice"),
+            ]
+        );
+        // A collapsed group keeps its header but hides its options and nested groups.
+        assert_eq!(
+            navigable_items(&entries, &groups, &HashSet::from([vec![10]]), false),
+            vec![Highlight::Group(vec![10]), value("kale"), value("rice")]
+        );
+        // Every group header is navigable in tree mode.
+        assert!(navigable_items(&entries, &groups, &HashSet::new(), true)
+            .contains(&Highlight::Group(vec![12])));
+    }
+
+    #[tokio::test]
+    async fn collapsible_group_headers_expose_their_state() {
+        #[function_component(App)]
+        fn app() -> Html {
+            html! {
+                <Select id="fruit" custom=true>
+                    <Group label="Berries" group=true collapsible=true default_collapsed=true>
+                        <Option value="strawberry" label="Strawberry" />
+                    </Group>
+                    <Group label="Citrus" group=true>
+                        <Option value="lemon" label="Lemon" />
+                    </Group>
+                </Select>
+            }
+        }
+
+        let html = render::<App>().await;
+        assert!(html.contains(r#"id="fruit-listbox-group-"#));
+        assert_eq!(
+            html.matches(r#"role="button" aria-expanded="false""#)
+                .count(),
+            1
+        );
+        assert_eq!(html.matches(r#"role="button""#).count(), 1);
+    }
+
+    #[test]
+    fn highlight_steps_stay_within_the_list() {
+        assert_eq!(step_highlight("ArrowDown", None, 3), Some(0));
+        assert_eq!(step_highlight("ArrowDown", Some(2), 3), Some(2));
+        assert_eq!(step_highlight("ArrowUp", None, 3), Some(2));
+        assert_eq!(step_highlight("ArrowUp", Some(0), 3), Some(0));
+        assert_eq!(step_highlight("Home", Some(2), 3), Some(0));
+        assert_eq!(step_highlight("End", Some(0), 3), Some(2));
+        assert_eq!(step_highlight("ArrowDown", None, 0), None);
+        assert_eq!(step_highlight("Enter", Some(1), 3), None);
+    }
+
+    #[test]
+    fn cascader_paths_complete_and_reload() {
+        let options = vec![CascaderOption::new("us", "United States")];
+        let state = Rc::new(CascaderState {
+            path: Vec::new(),
+            columns: vec![None],
+        });
+        let state = state.reduce(CascaderAction::Loaded {
+            level: 0,
+            path: Vec::new(),
+            options: options.clone(),
+        });
+        let state = state.reduce(CascaderAction::Choose {
+            level: 0,
+            value: "us".to_string(),
+            load_next: true,
+        });
+        assert!(!state.complete());
+        // Answers for a parent path that is no longer selected are ignored.
+        let state = state.reduce(CascaderAction::Loaded {
+            level: 1,
+            path: vec!["ca".to_string()],
+            options: options.clone(),
+        });
+        assert_eq!(state.columns[1], None);
+
+        let state = state.reduce(CascaderAction::Choose {
+            level: 1,
+            value: "ny".to_string(),
+            load_next: false,
+        });
+        assert!(state.complete());
+        assert_eq!(state.path, ["us", "ny"]);
+
+        // Fewer loaders cut the path, and every remaining level loads again.
+        let state = state.reduce(CascaderAction::Reload { levels: 1 });
+        assert_eq!(state.path, ["us"]);
+        assert_eq!(state.columns, [None]);
+        assert!(state.complete());
+    }
+
+    #[tokio::test]
+    async fn persisted_selection_replaces_the_defaults() {
+        thread_local! {
+            static STORAGE: MemoryStorage = MemoryStorage::default();
+        }
+
+        #[function_component(App)]
+        fn app() -> Html {
+            let persist = Persist::Store(Rc::new(STORAGE.with(Clone::clone)));
+            html! {
+                <Select name="status" multiple=true persist={persist} default_values={vec!["open".to_string()]}>
+                    <Option value="open" label="Open" />
+                    <Option value="closed" label="Closed" />
+                </Select>
+            }
+        }
+
+        // The chips list the selection of the first render.
+        let html = render::<App>().await;
+        assert!(html.contains(r#"aria-label="Remove open""#));
+        assert!(!html.contains(r#"aria-label="Remove closed""#));
+
+        STORAGE.with(|storage| storage.save("selectrs:status", &["closed".to_string()]));
+        let html = render::<App>().await;
+        assert!(!html.contains(r#"aria-label="Remove open""#));
+        assert!(html.contains(r#"aria-label="Remove closed""#));
+    }
+
+    #[test]
+    fn styles_are_joined_without_stray_spaces() {
+        assert_eq!(join_styles(&["", "", ""]), "");
+        assert_eq!(
+            join_styles(&["display: none;", "", "color: red;"]),
+            "display: none; color: red;"
+        );
+        assert_eq!(
+            join_styles(&[&indent_style("1rem", 2), " ", "font-weight: bold;"]),
+            "padding-inline-start: calc(2 * 1rem); font-weight: bold;"
+        );
+    }
+
+    #[tokio::test]
+    async fn chip_buttons_do_not_submit_forms() {
+        #[function_component(App)]
+        fn app() -> Html {
+            html! {
+                <form>
+                    <Select name="tags" multiple=true default_values={vec!["bug".to_string()]}>
+                        <Option value="bug" label="Bug" />
+                    </Select>
+                </form>
+            }
+        }
+
+        let html = render::<App>().await;
+        assert!(html.contains(r#"<button type="button" style="" aria-label="Remove bug""#));
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn config_select_renders_group_headers() {
+        #[function_component(App)]
+        fn app() -> Html {
+            let config: SelectConfig = serde_json::from_str(
+                r#"{
+                    "custom": true,
+                    "groups": [{ "label": "Berries", "collapsible": true }],
+                    "options": [
+                        { "value": "strawberry", "label": "Strawberry", "group": "Berries" },
+                        { "value": "apple", "label": "Apple" }
+                    ]
+                }"#,
+            )
+            .unwrap();
+            html! { <ConfigSelect config={config} /> }
+        }
+
+        let html = render::<App>().await;
+        assert!(html.contains(r#"<li role="group" aria-label="Berries""#));
+        assert!(html.contains(r#"data-state="expanded" class="selectrs-group""#));
+        assert!(html.contains(r#"class="selectrs-group-header">Berries"#));
+        assert_eq!(html.matches("selectrs-group-header").count(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn config_select_renders_native_optgroups() {
+        #[function_component(App)]
+        fn app() -> Html {
+            let config = SelectConfig {
+                options: vec![
+                    OptionConfig {
+                        value: "go".to_string(),
+                        label: "Go".to_string(),
+                        group: Some("Compiled".to_string()),
+                        ..OptionConfig::default()
+                    },
+                    OptionConfig {
+                        value: "python".to_string(),
+                        label: "Python".to_string(),
+                        ..OptionConfig::default()
+                    },
+                ],
+                ..SelectConfig::default()
+            };
+            html! { <ConfigSelect config={config} /> }
+        }
+
+        let html = render::<App>().await;
+        assert!(html.contains(r#"<optgroup label="Compiled""#));
+        assert_eq!(html.matches("<optgroup").count(), 1);
     }
 }
@@ -0,0 +1,46 @@
+//! Browser tests of the form data submitted by a native `Select`, run with `wasm-pack test --headless --firefox`.
+#![cfg(all(target_arch = "wasm32", feature = "yew"))]
+
+use selectrs::yew::{Option, Select};
+use std::time::Duration;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::{FormData, HtmlFormElement};
+use yew::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[function_component(App)]
+fn app() -> Html {
+    html! {
+        <form id="form">
+            <Select name="tags" multiple=true pinned={vec!["bug".to_string()]} default_values={vec!["bug".to_string()]}>
+                <Option value="bug" label="Bug" />
+                <Option value="docs" label="Docs" />
+            </Select>
+        </form>
+    }
+}
+
+#[wasm_bindgen_test]
+async fn pinned_selected_value_is_submitted_once() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+    yew::Renderer::<App>::with_root(root).render();
+    // Lets the options register and the selection sync to the DOM.
+    yew::platform::time::sleep(Duration::from_millis(50)).await;
+
+    let form: HtmlFormElement = document
+        .get_element_by_id("form")
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    let data = FormData::new_with_form(&form).unwrap();
+    let values: Vec<String> = data
+        .get_all("tags")
+        .iter()
+        .filter_map(|value| value.as_string())
+        .collect();
+    assert_eq!(values, ["bug"]);
+}